@@ -31,20 +31,14 @@ fn main() {
 
     compute_graph.step(); // Set data
 
-    let mut i = 0;
     while compute_graph.n_active() > 0 {
-        let time_start = std::time::Instant::now();
-
         compute_graph.push(|src, dst| graph_csr::compute::helper::atomic_min(src, dst, |v| v + 1));
         compute_graph.step();
 
-        let time_end = std::time::Instant::now();
-
-        i += 1;
         println!(
-            "Iteration {} took {}ms",
-            i,
-            (time_end - time_start).as_millis()
+            "Iteration {} done after {}ms",
+            compute_graph.iteration(),
+            compute_graph.elapsed().as_millis()
         );
     }
 
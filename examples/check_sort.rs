@@ -0,0 +1,26 @@
+fn main() {
+    let input_file = {
+        let args = std::env::args().skip(1).collect::<Vec<_>>();
+
+        if args.len() != 1 {
+            println!("Usage: check_sort <edge list file>");
+            return;
+        }
+
+        args[0].clone()
+    };
+
+    let source_file = std::fs::File::open(input_file).unwrap();
+
+    match graph_csr::Graph::<u64>::check_sorted(source_file) {
+        Ok(()) => println!("sorted"),
+        Err(e @ graph_csr::error::GraphError::UnsortedEdge { .. }) => {
+            println!("not sorted, {}", e);
+            std::process::exit(1);
+        }
+        Err(e) => {
+            println!("invalid edge list: {}", e);
+            std::process::exit(2);
+        }
+    }
+}
@@ -0,0 +1,1128 @@
+use atomic::Atomic;
+
+use num_traits::Float;
+use rand::Rng;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rayon::prelude::{IntoParallelIterator, IntoParallelRefIterator, ParallelIterator};
+
+/// Articulation points, bridges and other connectivity queries on undirected graphs
+pub mod connectivity;
+
+use super::{
+    bidirectional::Bidirectional,
+    compute::{
+        helper::{atomic_add, atomic_min},
+        ComputeGraph, WeightedComputeGraph,
+    },
+    util::{GraphData, ValidGraphType},
+    weighted::WeightedGraph,
+    Graph,
+};
+
+/// Runs a breadth-first search from `source` and returns the distance, in hops, from `source` to every node.
+/// Nodes that cannot be reached from `source` have a distance of `u32::MAX`.
+pub fn bfs<N>(graph: &Graph<N>, source: usize) -> Vec<u32>
+where
+    N: ValidGraphType + Send + Sync,
+{
+    let mut compute = ComputeGraph::<N, u32>::new(graph);
+
+    // Initialize nodes
+    compute.fill_active(false);
+    compute.fill_data(u32::MAX);
+
+    // Initialize source
+    compute.set_active(source, true);
+    compute.set_data(source, 0);
+    compute.step(); // Set data
+
+    while compute.n_active() > 0 {
+        compute.push(|src, dst| atomic_min(src, dst, |v| v + 1));
+        compute.step();
+    }
+
+    collect(compute.get_data_as_slice())
+}
+
+/// Estimates the eccentricity of every node, i.e. its largest distance to any other node, from breadth-first searches that start
+/// at `sample_size` nodes picked with `rng`, in parallel. Each node gets the largest distance seen to or from it, ignoring unreachable nodes.
+/// For graphs that store both directions of each edge, this is a lower bound that becomes exact once all nodes are sampled.
+pub fn approximate_eccentricities<N>(
+    graph: &Graph<N>,
+    sample_size: usize,
+    rng: &mut impl Rng,
+) -> Vec<u32>
+where
+    N: ValidGraphType + Send + Sync,
+{
+    let n_nodes = graph.n_nodes();
+    let sources = rand::seq::index::sample(rng, n_nodes, sample_size.min(n_nodes)).into_vec();
+
+    sources
+        .into_par_iter()
+        .map(|source| {
+            let distances = bfs(graph, source)
+                .into_iter()
+                .map(|d| if d == u32::MAX { 0 } else { d })
+                .collect::<Vec<_>>();
+
+            // The source itself is as far as the furthest node it reaches
+            let mut eccentricities = distances.clone();
+            eccentricities[source] = distances.iter().copied().max().unwrap_or(0);
+            eccentricities
+        })
+        .reduce(
+            || vec![0; n_nodes],
+            |a, b| a.into_iter().zip(b).map(|(a, b)| a.max(b)).collect(),
+        )
+}
+
+/// Returns the diameter of the graph, the largest eccentricity, from the eccentricities computed by [approximate_eccentricities].
+pub fn approximate_diameter(eccentricities: &[u32]) -> u32 {
+    eccentricities.iter().copied().max().unwrap_or(0)
+}
+
+/// Returns the radius of the graph, the smallest eccentricity, from the eccentricities computed by [approximate_eccentricities].
+pub fn approximate_radius(eccentricities: &[u32]) -> u32 {
+    eccentricities.iter().copied().min().unwrap_or(0)
+}
+
+/// Estimates the betweenness centrality of every node, i.e. the fraction of shortest paths between other nodes that go through it,
+/// with Brandes' algorithm from `samples` random sources, whose breadth-first searches run in parallel. The dependencies found from
+/// the sources are scaled up by `n / samples`, and the result is normalized by `1 / ((n - 1)(n - 2))`, the number of ordered pairs
+/// of other nodes. Sampling every node gives the exact centrality.
+pub fn betweenness_centrality_approx<N>(
+    graph: &Graph<N>,
+    samples: usize,
+    rng: &mut impl Rng,
+) -> Vec<f64>
+where
+    N: ValidGraphType + Send + Sync,
+{
+    let n_nodes = graph.n_nodes();
+    let samples = samples.min(n_nodes);
+    if n_nodes < 3 || samples == 0 {
+        return vec![0.0; n_nodes];
+    }
+
+    let sources = rand::seq::index::sample(rng, n_nodes, samples).into_vec();
+    let centrality = sources
+        .into_par_iter()
+        .map(|source| {
+            // Breadth-first search, counting the shortest paths to every node
+            let mut distances = vec![u32::MAX; n_nodes];
+            let mut paths = vec![0.0f64; n_nodes];
+            let mut order = Vec::with_capacity(n_nodes);
+            distances[source] = 0;
+            paths[source] = 1.0;
+            order.push(source);
+
+            let mut i = 0;
+            while i < order.len() {
+                let node = order[i];
+                for neighbor in graph.neighbors(node).iter().map(|x| x.as_()) {
+                    if distances[neighbor] == u32::MAX {
+                        distances[neighbor] = distances[node] + 1;
+                        order.push(neighbor);
+                    }
+                    if distances[neighbor] == distances[node] + 1 {
+                        paths[neighbor] += paths[node];
+                    }
+                }
+                i += 1;
+            }
+
+            // Accumulate the dependencies from the furthest nodes back to the source
+            let mut dependencies = vec![0.0f64; n_nodes];
+            for node in order.into_iter().rev() {
+                for neighbor in graph.neighbors(node).iter().map(|x| x.as_()) {
+                    if distances[neighbor] == distances[node] + 1 {
+                        dependencies[node] +=
+                            paths[node] / paths[neighbor] * (1.0 + dependencies[neighbor]);
+                    }
+                }
+            }
+            dependencies[source] = 0.0;
+
+            dependencies
+        })
+        .reduce(
+            || vec![0.0; n_nodes],
+            |a, b| a.into_iter().zip(b).map(|(a, b)| a + b).collect(),
+        );
+
+    let scale = n_nodes as f64 / samples as f64 / ((n_nodes - 1) * (n_nodes - 2)) as f64;
+    centrality.into_iter().map(|c| c * scale).collect()
+}
+
+/// Computes the shortest path distance from `source` to every node, following the edge weights.
+/// This is a Bellman-Ford variant where only the nodes improved in the last iteration relax their edges.
+/// Weights must not be negative. Nodes that cannot be reached from `source` have a distance of `W::infinity()`.
+pub fn sssp_weighted<N, W>(graph: &WeightedGraph<N, W>, source: usize) -> Vec<W>
+where
+    N: ValidGraphType + Send + Sync,
+    W: GraphData + Float + std::fmt::Debug,
+{
+    let mut compute = WeightedComputeGraph::<N, W, W>::new(graph);
+
+    // Initialize nodes
+    compute.fill_active(false);
+    compute.fill_data(W::infinity());
+
+    // Initialize source
+    compute.set_active(source, true);
+    compute.set_data(source, W::zero());
+    compute.step(); // Set data
+
+    while compute.n_active() > 0 {
+        compute.push_weighted(|src, weight, dst| atomic_min(src, dst, |v| v + weight));
+        compute.step();
+    }
+
+    collect(compute.get_data_as_slice())
+}
+
+/// Computes the PageRank of every node, with the given `damping` factor.
+/// Each iteration pushes `damping * rank / out_degree` from every node to its neighbors, on top of a `(1 - damping) / n` teleport term.
+/// The rank of nodes without outgoing edges is spread evenly across all nodes, so that the ranks always sum up to 1.
+/// Stops after `max_iter` iterations, or once the L1 norm of the change in the ranks falls below `tol`.
+pub fn pagerank<N>(graph: &Graph<N>, damping: f64, max_iter: u32, tol: f64) -> Vec<f64>
+where
+    N: ValidGraphType + Send + Sync,
+{
+    let n_nodes = graph.n_nodes();
+    if n_nodes == 0 {
+        return Vec::new();
+    }
+
+    let degrees = (0..n_nodes).map(|i| graph.degree(i)).collect::<Vec<_>>();
+    let mut ranks = vec![1.0 / n_nodes as f64; n_nodes];
+    let mut compute = ComputeGraph::<N, f64>::new(graph);
+
+    for _ in 0..max_iter {
+        // Each node sends its contribution, and nodes without edges spread theirs evenly
+        let mut dangling = 0.0;
+        for (i, rank) in ranks.iter().enumerate() {
+            if degrees[i] > 0 {
+                compute.set_data(i, damping * rank / degrees[i] as f64);
+            } else {
+                compute.set_data(i, 0.0);
+                dangling += damping * rank;
+            }
+        }
+        compute.fill_active(true);
+        compute.step(); // Set contributions
+
+        compute.fill_data((1.0 - damping + dangling) / n_nodes as f64);
+        compute.push(|contribution, dst| {
+            atomic_add(contribution, dst, |v| v);
+            false
+        });
+        compute.step();
+
+        let new_ranks = collect(compute.get_data_as_slice());
+        let change = ranks
+            .iter()
+            .zip(new_ranks.iter())
+            .map(|(old, new)| (old - new).abs())
+            .sum::<f64>();
+        ranks = new_ranks;
+
+        if change < tol {
+            break;
+        }
+    }
+
+    ranks
+}
+
+/// Computes the personalized PageRank of every node with respect to `source`, with the given `damping` factor.
+/// Same as [pagerank], except the `(1 - damping)` teleport term and the rank of nodes without outgoing edges always go back
+/// to `source`, so the result is the probability of ending at each node in a random walk that restarts at `source`.
+/// Runs exactly `max_iter` iterations. Panics if `source` is not a node of the graph.
+pub fn personalized_pagerank<N>(
+    graph: &Graph<N>,
+    source: usize,
+    damping: f64,
+    max_iter: u32,
+) -> Vec<f64>
+where
+    N: ValidGraphType + Send + Sync,
+{
+    let n_nodes = graph.n_nodes();
+    assert!(source < n_nodes, "the source must be a node of the graph");
+
+    let degrees = (0..n_nodes).map(|i| graph.degree(i)).collect::<Vec<_>>();
+    let mut ranks = vec![0.0; n_nodes];
+    ranks[source] = 1.0;
+    let mut compute = ComputeGraph::<N, f64>::new(graph);
+
+    for _ in 0..max_iter {
+        // Each node sends its contribution, and nodes without edges send theirs back to the source
+        let mut dangling = 0.0;
+        for (i, rank) in ranks.iter().enumerate() {
+            if degrees[i] > 0 {
+                compute.set_data(i, damping * rank / degrees[i] as f64);
+            } else {
+                compute.set_data(i, 0.0);
+                dangling += damping * rank;
+            }
+        }
+        compute.fill_active(true);
+        compute.step(); // Set contributions
+
+        compute.fill_data(0.0);
+        compute.set_data(source, 1.0 - damping + dangling);
+        compute.push(|contribution, dst| {
+            atomic_add(contribution, dst, |v| v);
+            false
+        });
+        compute.step();
+
+        ranks = collect(compute.get_data_as_slice());
+    }
+
+    ranks
+}
+
+/// Counts the triangles of the graph, by intersecting the neighbor lists of both ends of each edge.
+/// A triangle is a set of nodes `u < v < w` with the edges `u -> v`, `u -> w` and `v -> w`, so for graphs that store
+/// both directions of each edge, this is the number of undirected triangles.
+/// Panics if the neighbor lists are not sorted, see [Graph::has_sorted_neighbors].
+pub fn triangle_count<N>(graph: &Graph<N>) -> u64
+where
+    N: ValidGraphType + Send + Sync,
+{
+    assert!(
+        graph.has_sorted_neighbors(),
+        "triangle counting requires sorted neighbor lists"
+    );
+
+    graph
+        .par_iter()
+        .map(|(u, u_neighbors)| {
+            let mut count = 0;
+            for v in u_neighbors.iter().map(|v| v.as_()).filter(|v| *v > u) {
+                count += graph
+                    .neighbors(v)
+                    .iter()
+                    .filter(|w| w.as_() > v)
+                    .filter(|w| {
+                        u_neighbors
+                            .binary_search_by_key(&w.as_(), |x| x.as_())
+                            .is_ok()
+                    })
+                    .count() as u64;
+            }
+            count
+        })
+        .sum()
+}
+
+/// Computes the core number of every node, which is the largest `k` such that the node belongs to a subgraph
+/// where all nodes have a degree of at least `k`.
+/// The graph is treated as undirected, so both directions of each edge should be stored.
+/// Nodes are peeled in increasing order of their remaining degree, which is kept in a separate array as the graph is immutable.
+pub fn k_core_decomposition<N>(graph: &Graph<N>) -> Vec<usize>
+where
+    N: ValidGraphType,
+{
+    let n_nodes = graph.n_nodes();
+    let mut degrees = (0..n_nodes).map(|i| graph.degree(i)).collect::<Vec<_>>();
+    let max_degree = degrees.iter().copied().max().unwrap_or(0);
+
+    // Sort the nodes by degree with a bucket sort, keeping where each bucket starts
+    let mut bins = vec![0; max_degree + 1];
+    for degree in degrees.iter() {
+        bins[*degree] += 1;
+    }
+    let mut start = 0;
+    for bin in bins.iter_mut() {
+        let size = *bin;
+        *bin = start;
+        start += size;
+    }
+
+    let mut positions = vec![0; n_nodes];
+    let mut order = vec![0; n_nodes];
+    for (node, degree) in degrees.iter().enumerate() {
+        positions[node] = bins[*degree];
+        order[positions[node]] = node;
+        bins[*degree] += 1;
+    }
+    bins.rotate_right(1);
+    bins[0] = 0;
+
+    // Peel the node with the lowest degree, and move each of its neighbors one bucket down
+    for i in 0..n_nodes {
+        let node = order[i];
+        for neighbor in graph.neighbors(node).iter().map(|x| x.as_()) {
+            if degrees[neighbor] > degrees[node] {
+                let degree = degrees[neighbor];
+                let first = order[bins[degree]];
+
+                if neighbor != first {
+                    order.swap(positions[neighbor], bins[degree]);
+                    positions.swap(neighbor, first);
+                }
+                bins[degree] += 1;
+                degrees[neighbor] -= 1;
+            }
+        }
+    }
+
+    degrees
+}
+
+/// Orders the nodes so that every edge goes from an earlier node to a later one, with Kahn's algorithm.
+/// All nodes whose in-degree drops to zero at the same time are processed in parallel, and emitted in increasing order.
+/// Returns `None` if the graph has a cycle, as then not all nodes can be emitted.
+pub fn topological_sort<N>(graph: &Graph<N>) -> Option<Vec<usize>>
+where
+    N: ValidGraphType + Send + Sync,
+{
+    let in_degrees = graph
+        .in_degrees()
+        .into_iter()
+        .map(AtomicUsize::new)
+        .collect::<Vec<_>>();
+
+    let mut frontier = (0..graph.n_nodes())
+        .filter(|node| in_degrees[*node].load(Ordering::Relaxed) == 0)
+        .collect::<Vec<_>>();
+    let mut order = Vec::with_capacity(graph.n_nodes());
+
+    while !frontier.is_empty() {
+        order.extend_from_slice(&frontier);
+
+        // The last edge to reach a node releases it into the next frontier
+        let mut next = frontier
+            .par_iter()
+            .flat_map_iter(|node| {
+                graph.neighbors(*node).iter().filter_map(|neighbor| {
+                    (in_degrees[neighbor.as_()].fetch_sub(1, Ordering::Relaxed) == 1)
+                        .then_some(neighbor.as_())
+                })
+            })
+            .collect::<Vec<_>>();
+        next.sort_unstable();
+
+        frontier = next;
+    }
+
+    (order.len() == graph.n_nodes()).then_some(order)
+}
+
+/// Computes the local clustering coefficient of every node, which is the fraction of ordered pairs of its neighbors
+/// `(a, b)` with an edge `a -> b`. For graphs that store both directions of each edge, this is the undirected coefficient.
+/// Self loops are ignored, and nodes with less than 2 neighbors have a coefficient of 0.
+/// Panics if the neighbor lists are not sorted, see [Graph::has_sorted_neighbors].
+pub fn local_clustering_coefficient<N>(graph: &Graph<N>) -> Vec<f64>
+where
+    N: ValidGraphType + Send + Sync,
+{
+    assert!(
+        graph.has_sorted_neighbors(),
+        "the clustering coefficient requires sorted neighbor lists"
+    );
+
+    (0..graph.n_nodes())
+        .into_par_iter()
+        .map(|node| {
+            let neighbors = graph
+                .neighbors(node)
+                .iter()
+                .map(|x| x.as_())
+                .filter(|x| *x != node)
+                .collect::<Vec<_>>();
+            let degree = neighbors.len();
+            if degree < 2 {
+                return 0.0;
+            }
+
+            let links = neighbors
+                .iter()
+                .map(|a| {
+                    graph
+                        .neighbors(*a)
+                        .iter()
+                        .filter(|b| b.as_() != *a && neighbors.binary_search(&b.as_()).is_ok())
+                        .count()
+                })
+                .sum::<usize>();
+
+            links as f64 / (degree * (degree - 1)) as f64
+        })
+        .collect()
+}
+
+/// Computes the Jaccard similarity of the neighborhoods of `a` and `b`, `|N(a) ∩ N(b)| / |N(a) ∪ N(b)|`, by merging both neighbor lists.
+/// Repeated edges are counted once, and the similarity is 0 if neither node has neighbors.
+/// Panics if either neighbor list is not sorted, see [Graph::has_sorted_neighbors].
+pub fn jaccard_similarity<N>(graph: &Graph<N>, a: usize, b: usize) -> f64
+where
+    N: ValidGraphType,
+{
+    let a = graph.neighbors(a);
+    let b = graph.neighbors(b);
+    assert!(
+        a.windows(2).all(|w| w[0] <= w[1]) && b.windows(2).all(|w| w[0] <= w[1]),
+        "the Jaccard similarity requires sorted neighbor lists"
+    );
+
+    let (mut i, mut j) = (0, 0);
+    let (mut intersection, mut union) = (0usize, 0usize);
+    while i < a.len() || j < b.len() {
+        let next = match (a.get(i), b.get(j)) {
+            (Some(x), Some(y)) if x == y => {
+                intersection += 1;
+                *x
+            }
+            (Some(x), Some(y)) => {
+                if x < y {
+                    *x
+                } else {
+                    *y
+                }
+            }
+            (Some(x), None) => *x,
+            (None, Some(y)) => *y,
+            (None, None) => unreachable!(),
+        };
+        union += 1;
+
+        // Skip every copy of the value just counted
+        while a.get(i) == Some(&next) {
+            i += 1;
+        }
+        while b.get(j) == Some(&next) {
+            j += 1;
+        }
+    }
+
+    if union == 0 {
+        0.0
+    } else {
+        intersection as f64 / union as f64
+    }
+}
+
+/// Same as [jaccard_similarity], for each of the `pairs` of nodes in parallel.
+pub fn all_pairs_jaccard<N>(graph: &Graph<N>, pairs: &[(usize, usize)]) -> Vec<f64>
+where
+    N: ValidGraphType + Send + Sync,
+{
+    pairs
+        .par_iter()
+        .map(|(a, b)| jaccard_similarity(graph, *a, *b))
+        .collect()
+}
+
+/// Computes the weakly connected components of the graph, and returns a label for every node.
+/// Two nodes share the same label iff they are in the same component, and each label is the lowest node id of its component.
+/// Since the graph is directed, the labels are propagated with a push followed by a pull, so that both directions of each edge are used.
+pub fn wcc<N>(graph: &Graph<N>) -> Vec<usize>
+where
+    N: ValidGraphType + Send + Sync,
+{
+    let mut compute = ComputeGraph::<N, usize>::new(graph);
+
+    // Initialize
+    compute.fill_active(true);
+    for i in 0..graph.n_nodes() {
+        compute.set_data(i, i);
+    }
+    compute.step(); // Set data
+
+    while compute.n_active() > 0 {
+        // Forward direction, from active nodes to their neighbors
+        compute.push(|src, dst| atomic_min(src, dst, |v| v));
+
+        // Backward direction, from the neighbors to each node
+        compute.pull(|_, neighbors, old, new| {
+            neighbors.iter().fold(false, |status, neighbor| {
+                let local = old[neighbor.as_()].load(atomic::Ordering::Relaxed);
+                atomic_min(local, new, |v| v) || status
+            })
+        });
+        compute.step();
+    }
+
+    collect(compute.get_data_as_slice())
+}
+
+/// Detects communities by label propagation: every node starts with its own label, and then repeatedly takes the most frequent
+/// label among its neighbors, until no label changes or `max_iter` iterations are done. Ties keep the current label if it is
+/// one of the most frequent, and otherwise go to the lowest label, so the result is deterministic.
+/// The labels are renumbered to `0..num_communities`, in order of the first node of each community.
+/// For graphs that store both directions of each edge, every neighbor votes; otherwise only the outgoing neighbors do.
+pub fn label_propagation<N>(graph: &Graph<N>, max_iter: u32) -> Vec<usize>
+where
+    N: ValidGraphType + Send + Sync,
+{
+    let mut compute = ComputeGraph::<N, usize>::new(graph);
+
+    // Initialize
+    compute.initialize_with(|idx| (idx, true));
+    compute.step(); // Set data
+
+    for _ in 0..max_iter {
+        compute.pull(|idx, neighbors, old, new| {
+            let mut labels = neighbors
+                .iter()
+                .map(|neighbor| old[neighbor.as_()].load(atomic::Ordering::Relaxed))
+                .collect::<Vec<_>>();
+            labels.sort_unstable();
+
+            // The most frequent label, with the lowest one winning ties
+            let current = old[idx].load(atomic::Ordering::Relaxed);
+            let (mut best, mut best_count, mut current_count) = (current, 0, 0);
+            for run in labels.chunk_by(|a, b| a == b) {
+                if run.len() > best_count {
+                    (best, best_count) = (run[0], run.len());
+                }
+                if run[0] == current {
+                    current_count = run.len();
+                }
+            }
+
+            if best_count > current_count {
+                new.store(best, atomic::Ordering::Relaxed);
+                true
+            } else {
+                false
+            }
+        });
+        compute.step();
+
+        if compute.n_active() == 0 {
+            break;
+        }
+    }
+
+    // Renumber the labels in order of appearance
+    let mut renaming = vec![usize::MAX; graph.n_nodes()];
+    let mut n_communities = 0;
+    collect(compute.get_data_as_slice())
+        .into_iter()
+        .map(|label| {
+            if renaming[label] == usize::MAX {
+                renaming[label] = n_communities;
+                n_communities += 1;
+            }
+            renaming[label]
+        })
+        .collect()
+}
+
+/// Returns the number of distinct weakly connected components in the labels computed by [wcc].
+pub fn wcc_count(labels: &[usize]) -> usize {
+    labels
+        .iter()
+        .enumerate()
+        .filter(|(node, label)| node == *label)
+        .count()
+}
+
+/// Computes the strongly connected components of the graph with Kosaraju's algorithm, and returns a label for every node.
+/// Two nodes share the same label iff they are in the same component, and each label is the lowest node id of its component,
+/// so [wcc_count] also counts the components. Both depth-first searches are iterative, so deep graphs do not overflow the stack.
+pub fn scc<N>(graph: &Bidirectional<N>) -> Vec<usize>
+where
+    N: ValidGraphType + Send + Sync,
+{
+    let n_nodes = graph.n_nodes();
+
+    // Forward pass, the nodes in the order their search finishes
+    let mut visited = vec![false; n_nodes];
+    let mut finished = Vec::with_capacity(n_nodes);
+    let mut stack = Vec::new();
+    for root in 0..n_nodes {
+        if visited[root] {
+            continue;
+        }
+        visited[root] = true;
+        stack.push((root, 0));
+
+        while let Some((node, next)) = stack.last_mut() {
+            let node = *node;
+            match graph.out_neighbors(node).get(*next) {
+                Some(neighbor) => {
+                    *next += 1;
+                    let neighbor = neighbor.as_();
+                    if !visited[neighbor] {
+                        visited[neighbor] = true;
+                        stack.push((neighbor, 0));
+                    }
+                }
+                None => {
+                    finished.push(node);
+                    stack.pop();
+                }
+            }
+        }
+    }
+
+    // Reverse pass, every node reached from a root in decreasing finish order belongs to its component
+    let mut labels = vec![usize::MAX; n_nodes];
+    let mut stack = Vec::new();
+    let mut members = Vec::new();
+    for root in finished.into_iter().rev() {
+        if labels[root] != usize::MAX {
+            continue;
+        }
+        labels[root] = root;
+        stack.push(root);
+
+        while let Some(node) = stack.pop() {
+            members.push(node);
+            for neighbor in graph.in_neighbors(node).iter().map(|x| x.as_()) {
+                if labels[neighbor] == usize::MAX {
+                    labels[neighbor] = root;
+                    stack.push(neighbor);
+                }
+            }
+        }
+
+        let label = *members.iter().min().unwrap();
+        for node in members.drain(..) {
+            labels[node] = label;
+        }
+    }
+
+    labels
+}
+
+/// Loads the values of a slice of atomics into a vector.
+fn collect<T>(data: &[Atomic<T>]) -> Vec<T>
+where
+    T: Copy,
+{
+    data.iter()
+        .map(|x| x.load(atomic::Ordering::Relaxed))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_graph<'a, T>(edge_list: Vec<(T, T)>) -> Graph<'a, T>
+    where
+        T: ValidGraphType,
+    {
+        // Generate random filename
+        let destination_folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+
+        Graph::<T>::from_adjacency_list(
+            edge_list.into_iter().map(Ok),
+            destination_folder_name.as_str(),
+        )
+        .unwrap()
+    }
+
+    fn get_basic_graph<'a>() -> Graph<'a, u32> {
+        get_graph(vec![(0u32, 1u32), (0, 2), (1, 5), (1, 2), (4, 7)])
+    }
+
+    #[test]
+    fn bfs_disconnected() {
+        let graph = get_basic_graph();
+
+        assert_eq!(
+            bfs(&graph, 0),
+            vec![0, 1, 1, u32::MAX, u32::MAX, 2, u32::MAX, u32::MAX]
+        );
+        assert_eq!(
+            bfs(&graph, 4),
+            vec![
+                u32::MAX,
+                u32::MAX,
+                u32::MAX,
+                u32::MAX,
+                0,
+                u32::MAX,
+                u32::MAX,
+                1
+            ]
+        );
+    }
+
+    #[test]
+    fn bfs_cycle() {
+        let graph = get_graph(vec![
+            (0u32, 1u32),
+            (1, 2),
+            (2, 3),
+            (3, 4),
+            (4, 5),
+            (5, 6),
+            (6, 7),
+            (7, 0),
+        ]);
+
+        assert_eq!(bfs(&graph, 0), vec![0, 1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(bfs(&graph, 5), vec![3, 4, 5, 6, 7, 0, 1, 2]);
+    }
+
+    #[test]
+    fn wcc_basic() {
+        let graph = get_basic_graph();
+        let labels = wcc(&graph);
+
+        assert_eq!(labels, vec![0, 0, 0, 3, 4, 0, 6, 4]);
+        assert_eq!(wcc_count(&labels), 4);
+
+        // Nodes 0, 1, 2 and 5 share a component, 4 and 7 share another one, and 3 and 6 are isolated
+        assert!([1, 2, 5].iter().all(|node| labels[*node] == labels[0]));
+        assert_eq!(labels[7], labels[4]);
+        assert!([3, 4, 6].iter().all(|node| labels[*node] != labels[0]));
+    }
+
+    #[test]
+    fn wcc_reverse_edges() {
+        // Components are only connected through edges pointing to lower ids
+        let graph = get_graph(vec![(1u32, 0u32), (3, 2), (4, 3), (4, 1)]);
+
+        assert_eq!(wcc(&graph), vec![0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn bfs_single_node() {
+        let graph = Graph::<u32>::from_csr_vecs(vec![0, 0], vec![]).unwrap();
+
+        assert_eq!(bfs(&graph, 0), vec![0]);
+    }
+
+    #[test]
+    fn eccentricities() {
+        // Undirected path 0 - 1 - 2 - 3 - 4, with a leaf 5 attached to 2
+        let edges = [(0u32, 1u32), (1, 2), (2, 3), (3, 4), (2, 5)];
+        let mut edges = edges
+            .iter()
+            .flat_map(|&(a, b)| [(a, b), (b, a)])
+            .collect::<Vec<_>>();
+        edges.sort_unstable();
+        let graph = get_graph(edges);
+        let mut rng = <rand::rngs::StdRng as rand::SeedableRng>::seed_from_u64(42);
+
+        // Sampling every node is exact
+        let exact = approximate_eccentricities(&graph, 10, &mut rng);
+        assert_eq!(exact, vec![4, 3, 2, 3, 4, 3]);
+        assert_eq!(approximate_diameter(&exact), 4);
+        assert_eq!(approximate_radius(&exact), 2);
+
+        // Fewer samples only give lower bounds
+        let approximate = approximate_eccentricities(&graph, 2, &mut rng);
+        assert!(approximate.iter().zip(exact.iter()).all(|(a, e)| a <= e));
+        assert!(approximate.iter().any(|e| *e > 0));
+
+        // The same seed samples the same sources
+        let mut rng = <rand::rngs::StdRng as rand::SeedableRng>::seed_from_u64(7);
+        let first = approximate_eccentricities(&graph, 2, &mut rng);
+        let mut rng = <rand::rngs::StdRng as rand::SeedableRng>::seed_from_u64(7);
+        assert_eq!(first, approximate_eccentricities(&graph, 2, &mut rng));
+    }
+
+    #[test]
+    fn betweenness() {
+        let mut rng = <rand::rngs::StdRng as rand::SeedableRng>::seed_from_u64(42);
+
+        // Undirected path 0 - 1 - 2 - 3 - 4
+        let path = get_graph(vec![
+            (0u32, 1u32),
+            (1, 0),
+            (1, 2),
+            (2, 1),
+            (2, 3),
+            (3, 2),
+            (3, 4),
+            (4, 3),
+        ]);
+        let exact = betweenness_centrality_approx(&path, 5, &mut rng);
+        let expected = [0.0, 0.5, 8.0 / 12.0, 0.5, 0.0];
+        assert!(exact
+            .iter()
+            .zip(expected.iter())
+            .all(|(a, b)| (a - b).abs() < 1e-9));
+
+        // The center of a star is on every shortest path between leaves
+        let star = get_graph(vec![(0u32, 1u32), (0, 2), (0, 3), (1, 0), (2, 0), (3, 0)]);
+        assert_eq!(
+            betweenness_centrality_approx(&star, 10, &mut rng),
+            vec![1.0, 0.0, 0.0, 0.0]
+        );
+
+        // Fewer samples still give a non-negative estimate for every node
+        let approx = betweenness_centrality_approx(&path, 3, &mut rng);
+        assert_eq!(approx.len(), 5);
+        assert!(approx.iter().all(|c| *c >= 0.0));
+    }
+
+    #[test]
+    fn sssp_road_network() {
+        // Junctions connected by one-way roads, with a faster parallel road from 0 to 1
+        let roads = vec![
+            (0u32, 1u32, 7.0f64),
+            (0, 1, 5.0),
+            (0, 2, 9.0),
+            (0, 5, 14.0),
+            (1, 2, 10.0),
+            (1, 3, 15.0),
+            (2, 3, 11.0),
+            (2, 5, 2.0),
+            (3, 4, 6.0),
+            (5, 4, 9.0),
+            (6, 0, 1.0),
+        ];
+        let destination_folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+        let graph = WeightedGraph::from_weighted_adjacency_list(
+            roads.into_iter().map(Ok),
+            &destination_folder_name,
+        )
+        .unwrap();
+
+        assert_eq!(
+            sssp_weighted(&graph, 0),
+            vec![0.0, 5.0, 9.0, 20.0, 20.0, 11.0, f64::INFINITY]
+        );
+        assert_eq!(
+            sssp_weighted(&graph, 6),
+            vec![1.0, 6.0, 10.0, 21.0, 21.0, 12.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn pagerank_sums_to_one() {
+        let graph = get_basic_graph();
+        let ranks = pagerank(&graph, 0.85, 100, 1e-10);
+
+        assert_eq!(ranks.len(), graph.n_nodes());
+        assert!((ranks.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+
+        // Nodes 2 and 5 are reached from 1, which is reached from 0
+        assert!(ranks[2] > ranks[1] && ranks[1] > ranks[0]);
+        assert!((ranks[3] - ranks[6]).abs() < 1e-12);
+    }
+
+    #[test]
+    fn pagerank_cycle_is_uniform() {
+        let graph = get_graph(vec![(0u32, 1u32), (1, 2), (2, 3), (3, 0)]);
+        let ranks = pagerank(&graph, 0.85, 100, 1e-10);
+
+        for rank in ranks {
+            assert!((rank - 0.25).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn personalized_pagerank_restarts_at_source() {
+        let graph = get_basic_graph();
+        let ranks = personalized_pagerank(&graph, 1, 0.85, 100);
+
+        assert_eq!(ranks.len(), graph.n_nodes());
+        assert!((ranks.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+
+        // Node 0 cannot be reached from 1, and the source keeps the most mass
+        assert_eq!(ranks[0], 0.0);
+        assert!(ranks.iter().all(|rank| *rank <= ranks[1]));
+    }
+
+    #[test]
+    fn personalized_pagerank_cycle() {
+        let graph = get_graph(vec![(0u32, 1u32), (1, 2), (2, 0)]);
+        let ranks = personalized_pagerank(&graph, 0, 0.5, 100);
+
+        // r0 = 0.5 + 0.5 * r2, r1 = 0.5 * r0 and r2 = 0.5 * r1
+        let expected = [4.0 / 7.0, 2.0 / 7.0, 1.0 / 7.0];
+        for (rank, expected) in ranks.iter().zip(expected) {
+            assert!((rank - expected).abs() < 1e-9);
+        }
+
+        // Without iterations, all the mass is at the source
+        assert_eq!(
+            personalized_pagerank(&graph, 2, 0.5, 0),
+            vec![0.0, 0.0, 1.0]
+        );
+    }
+
+    #[test]
+    fn triangle_count_k4() {
+        let mut edges = Vec::new();
+        for u in 0..4u32 {
+            for v in 0..4u32 {
+                if u != v {
+                    edges.push((u, v));
+                }
+            }
+        }
+        let graph = get_graph(edges);
+
+        assert_eq!(triangle_count(&graph), 4);
+        assert!(get_basic_graph().has_sorted_neighbors());
+        assert_eq!(
+            triangle_count(&get_graph(vec![(0u32, 1u32), (1, 2), (2, 3), (3, 0)])),
+            0
+        );
+        assert_eq!(
+            triangle_count(&get_graph(vec![(0u32, 1u32), (0, 2), (1, 2), (2, 3)])),
+            1
+        );
+    }
+
+    #[test]
+    fn k_core_clique_with_path() {
+        // A clique of 4 nodes, extended by the path 3 - 4 - 5
+        let mut edges = Vec::new();
+        for u in 0..4u32 {
+            for v in 0..4u32 {
+                if u != v {
+                    edges.push((u, v));
+                }
+            }
+        }
+        edges.extend([(3, 4), (4, 3), (4, 5), (5, 4)]);
+        edges.sort();
+        let graph = get_graph(edges);
+
+        assert_eq!(k_core_decomposition(&graph), vec![3, 3, 3, 3, 1, 1]);
+
+        // An edge and an isolated node
+        let graph = Graph::<u32>::from_csr_vecs(vec![0, 1, 2, 2], vec![1, 0]).unwrap();
+        assert_eq!(k_core_decomposition(&graph), vec![1, 1, 0]);
+    }
+
+    #[test]
+    fn topological_sort_dag() {
+        let graph = get_graph(vec![(0u32, 2u32), (1, 2), (1, 3), (2, 4), (3, 4), (5, 0)]);
+        let order = topological_sort(&graph).unwrap();
+
+        assert_eq!(order, vec![1, 5, 0, 3, 2, 4]);
+
+        // Every edge goes forward in the order
+        let mut position = vec![0; order.len()];
+        for (i, node) in order.iter().enumerate() {
+            position[*node] = i;
+        }
+        for (src, edges) in graph.iter().enumerate() {
+            for dst in edges {
+                assert!(position[src] < position[*dst as usize]);
+            }
+        }
+    }
+
+    #[test]
+    fn topological_sort_cycle() {
+        assert_eq!(
+            topological_sort(&get_graph(vec![(0u32, 1u32), (1, 2), (2, 3), (3, 1)])),
+            None
+        );
+        assert_eq!(
+            topological_sort(&get_graph(vec![(0u32, 1u32), (1, 1)])),
+            None
+        );
+    }
+
+    #[test]
+    fn topological_sort_isolated_nodes() {
+        // Nodes without any edges
+        let graph = Graph::<u32>::from_csr_vecs(vec![0, 0, 0], vec![]).unwrap();
+
+        assert_eq!(topological_sort(&graph), Some(vec![0, 1]));
+    }
+
+    #[test]
+    fn local_clustering() {
+        // A triangle 0 - 1 - 2, with node 3 hanging from 0
+        let graph = get_graph(vec![
+            (0u32, 1u32),
+            (0, 2),
+            (0, 3),
+            (1, 0),
+            (1, 2),
+            (2, 0),
+            (2, 1),
+            (3, 0),
+        ]);
+        let coefficients = local_clustering_coefficient(&graph);
+
+        assert!((coefficients[0] - 1.0 / 3.0).abs() < 1e-12);
+        assert_eq!(coefficients[1..], [1.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn label_propagation_two_cliques() {
+        // Two cliques of 4 nodes, 0..4 and 4..8, joined by the edge 3 - 4, plus the isolated node 8
+        let mut edges = Vec::new();
+        for clique in [0u32..4, 4..8] {
+            for a in clique.clone() {
+                for b in clique.clone().filter(|b| *b != a) {
+                    edges.push((a, b));
+                }
+            }
+        }
+        edges.extend([(3, 4), (4, 3), (8, 8)]);
+        edges.sort_unstable();
+        let graph = get_graph(edges);
+
+        let labels = label_propagation(&graph, 100);
+        assert_eq!(labels, vec![0, 0, 0, 0, 1, 1, 1, 1, 2]);
+
+        // No iterations leave every node in its own community
+        assert_eq!(label_propagation(&graph, 0), (0..9).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn jaccard() {
+        let graph = get_graph(vec![
+            (0u32, 2u32),
+            (0, 3),
+            (0, 4),
+            (1, 3),
+            (1, 4),
+            (1, 4),
+            (1, 5),
+        ]);
+
+        // {2, 3, 4} and {3, 4, 5} share 2 out of 4 neighbors
+        assert_eq!(jaccard_similarity(&graph, 0, 1), 0.5);
+        assert_eq!(jaccard_similarity(&graph, 1, 0), 0.5);
+        assert_eq!(jaccard_similarity(&graph, 1, 1), 1.0);
+        assert_eq!(jaccard_similarity(&graph, 0, 2), 0.0);
+        assert_eq!(jaccard_similarity(&graph, 2, 3), 0.0);
+
+        assert_eq!(
+            all_pairs_jaccard(&graph, &[(0, 1), (1, 1), (2, 5)]),
+            vec![0.5, 1.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn scc_three_components() {
+        // {0, 1, 2} and {3, 4} are cycles, connected by 2 -> 3, and 5 is only reached from 4
+        let edges = vec![(0u32, 1u32), (1, 2), (2, 0), (2, 3), (3, 4), (4, 3), (4, 5)];
+        let forward = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+        let reverse = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+        let graph =
+            Bidirectional::from_adjacency_list(edges.into_iter().map(Ok), &forward, &reverse)
+                .unwrap();
+
+        let labels = scc(&graph);
+
+        assert_eq!(labels, vec![0, 0, 0, 3, 3, 5]);
+        assert_eq!(wcc_count(&labels), 3);
+    }
+
+    #[test]
+    fn scc_long_path() {
+        // Deep enough to overflow the stack with a recursive search
+        let n = 200_000u32;
+        let edges = (0..n - 1).map(|i| Ok((i, i + 1))).chain([Ok((n - 1, 0))]);
+        let forward = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+        let reverse = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+        let graph = Bidirectional::from_adjacency_list(edges, &forward, &reverse).unwrap();
+
+        assert!(scc(&graph).iter().all(|label| *label == 0));
+    }
+}
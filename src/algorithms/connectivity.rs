@@ -0,0 +1,236 @@
+use crate::{util::ValidGraphType, Graph};
+
+/// Returns the sorted nodes whose removal disconnects their component, i.e. the articulation points.
+/// The graph is taken as undirected, so both directions of each edge should be stored.
+pub fn articulation_points<N>(graph: &Graph<N>) -> Vec<usize>
+where
+    N: ValidGraphType,
+{
+    let (is_articulation, _) = low_links(graph);
+
+    is_articulation
+        .into_iter()
+        .enumerate()
+        .filter_map(|(node, is_articulation)| is_articulation.then_some(node))
+        .collect()
+}
+
+/// Returns the sorted edges whose removal disconnects their component, i.e. the bridges, as `(a, b)` pairs with `a < b`.
+/// The graph is taken as undirected, so both directions of each edge should be stored.
+/// Parallel edges between two nodes are never bridges, since removing one of them keeps the nodes connected.
+pub fn bridges<N>(graph: &Graph<N>) -> Vec<(usize, usize)>
+where
+    N: ValidGraphType,
+{
+    let (_, mut bridges) = low_links(graph);
+    bridges.sort_unstable();
+    bridges
+}
+
+/// Counts the weakly connected components of the graph with a single sequential pass over the edges, using union-find.
+/// Gives the same count as [wcc_count](super::wcc_count) on the labels of [wcc](super::wcc), without iterating once per
+/// level of the diameter. Each edge joins the components of its endpoints, regardless of its direction.
+pub fn connected_components_count<N>(graph: &Graph<N>) -> usize
+where
+    N: ValidGraphType,
+{
+    let mut parents = (0..graph.n_nodes()).collect::<Vec<_>>();
+
+    for (src, neighbors) in graph.iter().enumerate() {
+        for dst in neighbors.iter() {
+            let a = find_root(&mut parents, src);
+            let b = find_root(&mut parents, dst.as_());
+            // The lower root is kept, so that the result does not depend on the order of the edges
+            if a != b {
+                parents[a.max(b)] = a.min(b);
+            }
+        }
+    }
+
+    (0..parents.len())
+        .filter(|node| parents[*node] == *node)
+        .count()
+}
+
+/// Returns the root of the set containing `node`, pointing every node on the way directly at it.
+fn find_root(parents: &mut [usize], node: usize) -> usize {
+    let mut root = node;
+    while parents[root] != root {
+        root = parents[root];
+    }
+
+    let mut node = node;
+    while parents[node] != root {
+        let next = parents[node];
+        parents[node] = root;
+        node = next;
+    }
+
+    root
+}
+
+/// Runs Tarjan's depth-first search over every component, tracking the discovery time of each node and the lowest
+/// discovery time reachable from its subtree. Returns which nodes are articulation points, and the bridges.
+/// The search uses an explicit stack, so graphs with long paths do not overflow the call stack.
+fn low_links<N>(graph: &Graph<N>) -> (Vec<bool>, Vec<(usize, usize)>)
+where
+    N: ValidGraphType,
+{
+    let n_nodes = graph.n_nodes();
+    let mut discovery = vec![usize::MAX; n_nodes];
+    let mut low = vec![usize::MAX; n_nodes];
+    let mut is_articulation = vec![false; n_nodes];
+    let mut bridges = Vec::new();
+    let mut time = 0;
+
+    // (node, parent, next edge to visit, whether the edge back to the parent was already skipped)
+    let mut stack = Vec::new();
+
+    for root in 0..n_nodes {
+        if discovery[root] != usize::MAX {
+            continue;
+        }
+        discovery[root] = time;
+        low[root] = time;
+        time += 1;
+        stack.push((root, usize::MAX, 0, false));
+        let mut root_children = 0;
+
+        while let Some((node, parent, next, skipped_parent)) = stack.last_mut() {
+            let node = *node;
+            let parent = *parent;
+
+            if let Some(neighbor) = graph.neighbors(node).get(*next) {
+                *next += 1;
+                let neighbor = neighbor.as_();
+
+                // Only one edge to the parent is the tree edge, any other one closes a cycle
+                if neighbor == parent && !*skipped_parent {
+                    *skipped_parent = true;
+                } else if discovery[neighbor] == usize::MAX {
+                    discovery[neighbor] = time;
+                    low[neighbor] = time;
+                    time += 1;
+                    if node == root {
+                        root_children += 1;
+                    }
+                    stack.push((neighbor, node, 0, false));
+                } else {
+                    low[node] = low[node].min(discovery[neighbor]);
+                }
+                continue;
+            }
+
+            stack.pop();
+            if parent == usize::MAX {
+                continue;
+            }
+
+            low[parent] = low[parent].min(low[node]);
+            if parent != root && low[node] >= discovery[parent] {
+                is_articulation[parent] = true;
+            }
+            if low[node] > discovery[parent] {
+                bridges.push((parent.min(node), parent.max(node)));
+            }
+        }
+
+        is_articulation[root] = root_children > 1;
+    }
+
+    (is_articulation, bridges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds an undirected graph from `edges`, storing both directions of each one.
+    fn get_undirected_graph<'a>(edges: &[(u32, u32)]) -> Graph<'a, u32> {
+        let mut edges = edges
+            .iter()
+            .flat_map(|(a, b)| [(*a, *b), (*b, *a)])
+            .collect::<Vec<_>>();
+        edges.sort_unstable();
+
+        Graph::from_adjacency_list(
+            edges.into_iter().map(Ok),
+            &format!("/tmp/tmp_dst_{}", rand::random::<u32>()),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn path() {
+        let graph = get_undirected_graph(&[(0, 1), (1, 2), (2, 3)]);
+
+        assert_eq!(articulation_points(&graph), vec![1, 2]);
+        assert_eq!(bridges(&graph), vec![(0, 1), (1, 2), (2, 3)]);
+    }
+
+    #[test]
+    fn cycle() {
+        let graph = get_undirected_graph(&[(0, 1), (1, 2), (2, 3), (3, 0)]);
+
+        assert!(articulation_points(&graph).is_empty());
+        assert!(bridges(&graph).is_empty());
+    }
+
+    #[test]
+    fn biconnected_components() {
+        // Two triangles {0, 1, 2} and {3, 4, 5} joined by the bridge 2 - 3, a tail 5 - 6 and a separate triangle {7, 8, 9}
+        let graph = get_undirected_graph(&[
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (2, 3),
+            (3, 4),
+            (4, 5),
+            (5, 3),
+            (5, 6),
+            (7, 8),
+            (8, 9),
+            (9, 7),
+        ]);
+
+        assert_eq!(articulation_points(&graph), vec![2, 3, 5]);
+        assert_eq!(bridges(&graph), vec![(2, 3), (5, 6)]);
+    }
+
+    #[test]
+    fn parallel_edges() {
+        let graph = get_undirected_graph(&[(0, 1), (0, 1), (1, 2)]);
+
+        assert_eq!(articulation_points(&graph), vec![1]);
+        assert_eq!(bridges(&graph), vec![(1, 2)]);
+    }
+
+    #[test]
+    fn components_count() {
+        let graph = get_undirected_graph(&[(0, 1), (1, 2), (3, 4), (6, 6)]);
+        // {0, 1, 2}, {3, 4}, {5} and {6}
+        assert_eq!(connected_components_count(&graph), 4);
+
+        // A single direction of each edge is enough
+        let graph = Graph::<u32>::from_adjacency_list(
+            vec![(0u32, 3u32), (2, 1), (4, 3)].into_iter().map(Ok),
+            &format!("/tmp/tmp_dst_{}", rand::random::<u32>()),
+        )
+        .unwrap();
+        assert_eq!(connected_components_count(&graph), 2);
+        assert_eq!(
+            connected_components_count(&graph),
+            crate::algorithms::wcc_count(&crate::algorithms::wcc(&graph))
+        );
+    }
+
+    #[test]
+    fn long_path() {
+        // Deep enough to overflow the stack with a recursive search
+        let n = 200_000u32;
+        let graph = get_undirected_graph(&(0..n - 1).map(|i| (i, i + 1)).collect::<Vec<_>>());
+
+        assert_eq!(articulation_points(&graph).len(), n as usize - 2);
+        assert_eq!(bridges(&graph).len(), n as usize - 1);
+    }
+}
@@ -0,0 +1,148 @@
+use super::{util::ValidGraphType, Graph};
+
+/// A graph that keeps, next to its own edges, a reverse graph with the incoming edges of every node.
+/// Both graphs live in their own folder, so each one can also be loaded and used on its own.
+/// Example:
+/// ```
+/// use graph_csr::bidirectional::Bidirectional;
+///
+/// let forward = format!("/tmp/bidirectional_doc_{}", rand::random::<u32>());
+/// let reverse = format!("{}_reverse", forward);
+/// let edges = vec![(0u32, 1u32), (0, 2), (1, 2)];
+///
+/// let graph = Bidirectional::from_adjacency_list(edges.into_iter().map(Ok), &forward, &reverse).unwrap();
+///
+/// assert_eq!(graph.out_neighbors(0), &[1, 2]);
+/// assert_eq!(graph.in_neighbors(2), &[0, 1]);
+/// ```
+pub struct Bidirectional<'a, N> {
+    forward: Graph<'a, N>,
+    reverse: Graph<'a, N>,
+}
+
+impl<'a, N> Bidirectional<'a, N>
+where
+    N: ValidGraphType + Send + Sync,
+{
+    /// Loads a graph from `forward_folder` and its reverse from `reverse_folder`.
+    /// Returns `InvalidData` if both graphs do not have the same number of nodes and edges.
+    pub fn from_folder(
+        forward_folder: &str,
+        reverse_folder: &str,
+    ) -> Result<Bidirectional<'a, N>, std::io::Error> {
+        let forward = Graph::load_graph(forward_folder)?;
+        let reverse = Graph::load_graph(reverse_folder)?;
+
+        if forward.n_nodes() != reverse.n_nodes() || forward.n_edges() != reverse.n_edges() {
+            Err(std::io::ErrorKind::InvalidData)?;
+        }
+
+        Ok(Bidirectional { forward, reverse })
+    }
+
+    /// Computes the reverse of `forward` with [Graph::transpose] and stores it in `reverse_folder`.
+    pub fn from_graph(
+        forward: Graph<'a, N>,
+        reverse_folder: &str,
+    ) -> Result<Bidirectional<'a, N>, std::io::Error> {
+        let reverse = forward.transpose(reverse_folder)?;
+
+        Ok(Bidirectional { forward, reverse })
+    }
+
+    /// Same as [Graph::from_adjacency_list], storing the graph in `forward_folder` and its reverse in `reverse_folder`.
+    pub fn from_adjacency_list<T>(
+        stream: T,
+        forward_folder: &str,
+        reverse_folder: &str,
+    ) -> Result<Bidirectional<'a, N>, std::io::Error>
+    where
+        T: Iterator<Item = std::io::Result<(N, N)>> + Sized,
+    {
+        Self::from_graph(
+            Graph::from_adjacency_list(stream, forward_folder)?,
+            reverse_folder,
+        )
+    }
+
+    /// Returns the graph with the outgoing edges.
+    pub fn forward(&self) -> &Graph<'a, N> {
+        &self.forward
+    }
+
+    /// Returns the graph with the incoming edges.
+    pub fn reverse(&self) -> &Graph<'a, N> {
+        &self.reverse
+    }
+
+    /// Returns the nodes `node` has an edge to.
+    pub fn out_neighbors(&self, node: usize) -> &[N] {
+        self.forward.neighbors(node)
+    }
+
+    /// Returns the nodes that have an edge to `node`.
+    pub fn in_neighbors(&self, node: usize) -> &[N] {
+        self.reverse.neighbors(node)
+    }
+
+    /// Returns the out-degree of `node`
+    pub fn out_degree(&self, node: usize) -> usize {
+        self.forward.degree(node)
+    }
+
+    /// Returns the in-degree of `node`
+    pub fn in_degree(&self, node: usize) -> usize {
+        self.reverse.degree(node)
+    }
+
+    /// Returns the number of nodes existing in the graph
+    pub fn n_nodes(&self) -> usize {
+        self.forward.n_nodes()
+    }
+
+    /// Returns the number of edges existing in the graph
+    pub fn n_edges(&self) -> usize {
+        self.forward.n_edges()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bidirectional() {
+        let edges = vec![(0u32, 1u32), (0, 2), (1, 5), (1, 2), (4, 7)];
+        let forward_folder = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+        let reverse_folder = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+
+        let graph = Bidirectional::from_adjacency_list(
+            edges.into_iter().map(Ok),
+            &forward_folder,
+            &reverse_folder,
+        )
+        .unwrap();
+
+        assert_eq!(graph.n_nodes(), 8);
+        assert_eq!(graph.n_edges(), 5);
+        assert_eq!(graph.out_neighbors(1), &[2, 5]);
+        assert_eq!(graph.in_neighbors(2), &[0, 1]);
+        assert_eq!(graph.in_neighbors(7), &[4]);
+        assert_eq!(graph.in_degree(0), 0);
+        assert_eq!(graph.out_degree(0), 2);
+
+        let loaded = Bidirectional::<u32>::from_folder(&forward_folder, &reverse_folder).unwrap();
+        assert_eq!(loaded.in_neighbors(5), &[1]);
+    }
+
+    #[test]
+    fn mismatched_folders() {
+        let forward_folder = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+        let other_folder = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+
+        Graph::from_adjacency_list([(0u32, 1u32)].into_iter().map(Ok), &forward_folder).unwrap();
+        Graph::from_adjacency_list([(0u32, 2u32)].into_iter().map(Ok), &other_folder).unwrap();
+
+        assert!(Bidirectional::<u32>::from_folder(&forward_folder, &other_folder).is_err());
+    }
+}
@@ -0,0 +1,423 @@
+use std::{collections::HashSet, marker::PhantomData};
+
+use super::{
+    error::GraphError, metadata::GraphMetadata, reading, util::ValidGraphType, ConstructionHint,
+    Graph,
+};
+
+/// How the construction handles edge lists that are not sorted by source.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum SortPolicy {
+    /// The input must already be sorted by source, otherwise the construction fails with `InvalidData`.
+    #[default]
+    RequireSorted,
+    /// The input is collected and sorted in memory before construction, so the whole edge list must fit in memory.
+    SortInMemory,
+    /// The input is sorted in chunks that are written to temporary files and then merged, so only one chunk is in memory at a time.
+    /// This allows constructing graphs whose edge list does not fit in memory.
+    ExternalSort(ExternalSortConfig),
+}
+
+/// What the construction of a [GraphBuilder] does when the destination folder already exists.
+/// The `from_*` constructors of [Graph] do not take a policy, and always behave as [OverwritePolicy::Reuse].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OverwritePolicy {
+    /// The folder is reused, and only the graph files in it are replaced, as in [Graph::from_adjacency_list].
+    /// This allows storing several graphs with different file names in the same folder.
+    #[default]
+    Reuse,
+    /// The construction fails with a [GraphError::DestinationExists] as the inner error, leaving the folder untouched.
+    Fail,
+    /// The folder and everything in it is deleted, and then created again before the construction.
+    Overwrite,
+}
+
+/// The configuration of [SortPolicy::ExternalSort].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExternalSortConfig {
+    /// How many bytes of edges are sorted in memory at a time, which is also the size of each temporary file.
+    pub chunk_size_bytes: usize,
+    /// The folder where the temporary files are written.
+    pub temp_dir: String,
+}
+
+impl Default for ExternalSortConfig {
+    /// Chunks of 256 MiB, written to the temporary folder of the system.
+    fn default() -> Self {
+        ExternalSortConfig {
+            chunk_size_bytes: 256 << 20,
+            temp_dir: std::env::temp_dir().to_string_lossy().into_owned(),
+        }
+    }
+}
+
+/// A builder for constructing graphs from adjacency lists with customizable behaviour.
+/// All options are applied while streaming the edges into the same file-writing loop used by [Graph::from_adjacency_list].
+/// Example:
+/// ```
+/// use graph_csr::builder::{GraphBuilder, SortPolicy};
+///
+/// let folder = format!("/tmp/builder_doc_{}", rand::random::<u32>());
+/// let edges = vec![(1u32, 2u32), (0, 1), (0, 1), (2, 2)];
+///
+/// let graph = GraphBuilder::<u32>::new()
+///     .sort_policy(SortPolicy::SortInMemory)
+///     .dedup(true)
+///     .remove_self_loops(true)
+///     .build_from_iter(edges.into_iter().map(Ok), &folder)
+///     .unwrap();
+///
+/// assert_eq!(graph.n_edges(), 2);
+/// ```
+pub struct GraphBuilder<N> {
+    sort_policy: SortPolicy,
+    overwrite_policy: OverwritePolicy,
+    dedup: bool,
+    remove_self_loops: bool,
+    parallel_construction: bool,
+    vertex_file: String,
+    edge_file: String,
+    _phantom: PhantomData<N>,
+}
+
+impl<N> Default for GraphBuilder<N> {
+    fn default() -> Self {
+        GraphBuilder {
+            sort_policy: SortPolicy::default(),
+            overwrite_policy: OverwritePolicy::default(),
+            dedup: false,
+            remove_self_loops: false,
+            parallel_construction: false,
+            vertex_file: reading::VERTEX_NAME.to_string(),
+            edge_file: reading::EDGE_NAME.to_string(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<N> GraphBuilder<N>
+where
+    N: ValidGraphType,
+{
+    /// Creates a new builder with the same behaviour as [Graph::from_adjacency_list].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how unsorted inputs are handled.
+    pub fn sort_policy(mut self, sort_policy: SortPolicy) -> Self {
+        self.sort_policy = sort_policy;
+        self
+    }
+
+    /// Sets what happens when the destination folder already exists.
+    pub fn overwrite_policy(mut self, overwrite_policy: OverwritePolicy) -> Self {
+        self.overwrite_policy = overwrite_policy;
+        self
+    }
+
+    /// Sets whether repeated edges between the same pair of nodes are kept only once.
+    pub fn dedup(mut self, dedup: bool) -> Self {
+        self.dedup = dedup;
+        self
+    }
+
+    /// Sets whether edges from a node to itself are discarded.
+    pub fn remove_self_loops(mut self, remove_self_loops: bool) -> Self {
+        self.remove_self_loops = remove_self_loops;
+        self
+    }
+
+    /// Sets whether the CSR arrays are built on all threads of the Rayon thread pool.
+    /// This collects the whole edge list in memory, after sorting and filtering, so it trades memory for speed on large inputs.
+    pub fn parallel_construction(mut self, parallel_construction: bool) -> Self {
+        self.parallel_construction = parallel_construction;
+        self
+    }
+
+    /// Sets the name of the vertex file inside the graph folder.
+    pub fn vertex_file(mut self, name: &str) -> Self {
+        self.vertex_file = name.to_string();
+        self
+    }
+
+    /// Sets the name of the edge file inside the graph folder.
+    pub fn edge_file(mut self, name: &str) -> Self {
+        self.edge_file = name.to_string();
+        self
+    }
+
+    /// Builds the graph from `stream`, stores it in `folder_name` and returns it.
+    pub fn build_from_iter<'a, T>(
+        self,
+        stream: T,
+        folder_name: &str,
+    ) -> Result<Graph<'a, N>, std::io::Error>
+    where
+        T: Iterator<Item = std::io::Result<(N, N)>> + Sized,
+        N: Send + Sync + 'a,
+    {
+        let exists = std::path::Path::new(folder_name).exists();
+        match self.overwrite_policy {
+            OverwritePolicy::Fail if exists => {
+                Err(GraphError::DestinationExists(folder_name.to_string()))?
+            }
+            OverwritePolicy::Overwrite if exists => std::fs::remove_dir_all(folder_name)?,
+            _ => {}
+        }
+
+        let files = match &self.sort_policy {
+            SortPolicy::RequireSorted => {
+                self.write(stream, folder_name, ConstructionHint::CheckSorted)?
+            }
+            SortPolicy::SortInMemory => {
+                let mut edges = stream.collect::<std::io::Result<Vec<_>>>()?;
+                edges.sort_unstable_by_key(|(src, dst)| (src.as_(), dst.as_()));
+
+                self.write(
+                    edges.into_iter().map(Ok),
+                    folder_name,
+                    ConstructionHint::TrustSorted,
+                )?
+            }
+            SortPolicy::ExternalSort(config) => {
+                let edges =
+                    reading::external_sort(stream, config.chunk_size_bytes, &config.temp_dir)?;
+
+                self.write(edges, folder_name, ConstructionHint::TrustSorted)?
+            }
+        };
+
+        // The metadata sidecar describes the default files of the folder
+        if self.vertex_file == reading::VERTEX_NAME && self.edge_file == reading::EDGE_NAME {
+            GraphMetadata::new::<N>(files.2 - 1, files.3)
+                .with_edges_sorted_per_node(true)
+                .save(folder_name)?;
+        }
+
+        Graph::load_graph_with_file_names(folder_name, &self.vertex_file, &self.edge_file)
+    }
+
+    /// Applies the edge filters to the sorted `stream` and writes it to `folder_name`.
+    fn write<T>(
+        &self,
+        stream: T,
+        folder_name: &str,
+        hint: ConstructionHint,
+    ) -> std::io::Result<reading::GraphFiles>
+    where
+        T: Iterator<Item = std::io::Result<(N, N)>> + Sized,
+        N: Send + Sync,
+    {
+        let remove_self_loops = self.remove_self_loops;
+        let dedup = self.dedup;
+
+        // Destinations already seen for the current source
+        let mut current = None;
+        let mut seen = HashSet::new();
+
+        let stream = stream
+            .filter(move |edge| !matches!(edge, Ok((src, dst)) if remove_self_loops && src == dst))
+            .filter(move |edge| match edge {
+                Ok((src, dst)) if dedup => {
+                    if current != Some(src.as_()) {
+                        current = Some(src.as_());
+                        seen.clear();
+                    }
+                    seen.insert(dst.as_())
+                }
+                _ => true,
+            });
+
+        if self.parallel_construction {
+            return reading::write_adjacency_list_parallel(
+                stream.collect::<std::io::Result<Vec<_>>>()?,
+                folder_name,
+                &self.vertex_file,
+                &self.edge_file,
+                hint,
+            );
+        }
+
+        reading::write_adjacency_list(
+            stream,
+            folder_name,
+            &self.vertex_file,
+            &self.edge_file,
+            hint,
+            0,
+            true,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_matches_adjacency_list() {
+        let edges = vec![(0u32, 1u32), (0, 2), (1, 5), (1, 2), (4, 7)];
+        let destination_folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+
+        let graph = GraphBuilder::<u32>::new()
+            .build_from_iter(edges.into_iter().map(Ok), &destination_folder_name)
+            .unwrap();
+
+        assert_eq!(
+            graph.iter().map(|x| x.to_vec()).collect::<Vec<_>>(),
+            vec![
+                vec![1, 2],
+                vec![2, 5],
+                vec![],
+                vec![],
+                vec![7],
+                vec![],
+                vec![],
+                vec![]
+            ]
+        );
+    }
+
+    #[test]
+    fn unsorted_is_rejected() {
+        let edges = vec![(1u32, 0u32), (0, 1)];
+        let destination_folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+
+        assert!(GraphBuilder::<u32>::new()
+            .build_from_iter(edges.into_iter().map(Ok), &destination_folder_name)
+            .is_err());
+    }
+
+    #[test]
+    fn sort_dedup_and_remove_self_loops() {
+        let edges = vec![(2u32, 0u32), (0, 1), (2, 2), (0, 2), (0, 1), (1, 1), (2, 0)];
+        let destination_folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+
+        let graph = GraphBuilder::<u32>::new()
+            .sort_policy(SortPolicy::SortInMemory)
+            .dedup(true)
+            .remove_self_loops(true)
+            .build_from_iter(edges.into_iter().map(Ok), &destination_folder_name)
+            .unwrap();
+
+        assert_eq!(
+            graph.iter().map(|x| x.to_vec()).collect::<Vec<_>>(),
+            vec![vec![1, 2], vec![], vec![0]]
+        );
+    }
+
+    #[test]
+    fn external_sort() {
+        let edges = (0..1000u32)
+            .map(|i| ((i * 7919) % 100, (i * 104729) % 100))
+            .collect::<Vec<_>>();
+        let temp_dir = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+        std::fs::create_dir(&temp_dir).unwrap();
+
+        // Chunks of 100 edges, so 10 runs are merged
+        let config = ExternalSortConfig {
+            chunk_size_bytes: 100 * 2 * std::mem::size_of::<u32>(),
+            temp_dir: temp_dir.clone(),
+        };
+        let external = GraphBuilder::<u32>::new()
+            .sort_policy(SortPolicy::ExternalSort(config))
+            .build_from_iter(
+                edges.clone().into_iter().map(Ok),
+                &format!("/tmp/tmp_dst_{}", rand::random::<u32>()),
+            )
+            .unwrap();
+
+        let in_memory = GraphBuilder::<u32>::new()
+            .sort_policy(SortPolicy::SortInMemory)
+            .build_from_iter(
+                edges.into_iter().map(Ok),
+                &format!("/tmp/tmp_dst_{}", rand::random::<u32>()),
+            )
+            .unwrap();
+
+        assert_eq!(external.as_csr_slices(), in_memory.as_csr_slices());
+        assert_eq!(std::fs::read_dir(&temp_dir).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn parallel_construction() {
+        let edges = (0..10_000u32)
+            .map(|i| ((i * 7919) % 1000, (i * 104729) % 1200))
+            .collect::<Vec<_>>();
+        let build = |parallel: bool| {
+            GraphBuilder::<u32>::new()
+                .sort_policy(SortPolicy::SortInMemory)
+                .dedup(true)
+                .parallel_construction(parallel)
+                .build_from_iter(
+                    edges.clone().into_iter().map(Ok),
+                    &format!("/tmp/tmp_dst_{}", rand::random::<u32>()),
+                )
+                .unwrap()
+        };
+
+        let sequential = build(false);
+        let parallel = build(true);
+        assert_eq!(parallel.n_nodes(), 1200);
+        assert_eq!(parallel.as_csr_slices(), sequential.as_csr_slices());
+        assert!(parallel.has_sorted_neighbors());
+
+        // The sort check still applies
+        assert!(GraphBuilder::<u32>::new()
+            .parallel_construction(true)
+            .build_from_iter(
+                vec![(1u32, 0u32), (0, 1)].into_iter().map(Ok),
+                &format!("/tmp/tmp_dst_{}", rand::random::<u32>()),
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn overwrite_policy() {
+        let destination_folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+        let build = |edges: Vec<(u32, u32)>, policy| {
+            GraphBuilder::<u32>::new()
+                .overwrite_policy(policy)
+                .build_from_iter(edges.into_iter().map(Ok), &destination_folder_name)
+        };
+
+        build(vec![(0, 1), (0, 2), (1, 2), (2, 3)], OverwritePolicy::Fail).unwrap();
+        let marker = format!("{}/marker", destination_folder_name);
+        std::fs::write(&marker, "").unwrap();
+
+        let error = build(vec![(0, 1)], OverwritePolicy::Fail).err().unwrap();
+        assert_eq!(error.kind(), std::io::ErrorKind::AlreadyExists);
+        assert!(error.to_string().contains(&destination_folder_name));
+        assert!(matches!(
+            error.get_ref().unwrap().downcast_ref::<GraphError>(),
+            Some(GraphError::DestinationExists(_))
+        ));
+
+        // Reusing replaces the larger graph files, and keeps the rest of the folder
+        let graph = build(vec![(0, 1)], OverwritePolicy::Reuse).unwrap();
+        assert_eq!(graph.as_csr_slices(), (&[0usize, 1, 1][..], &[1u32][..]));
+        assert!(std::path::Path::new(&marker).exists());
+
+        let graph = build(vec![(1, 0)], OverwritePolicy::Overwrite).unwrap();
+        assert_eq!(graph.neighbors(1), &[0]);
+        assert!(!std::path::Path::new(&marker).exists());
+    }
+
+    #[test]
+    fn custom_file_names() {
+        let edges = vec![(0u32, 1u32), (1, 0)];
+        let destination_folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+
+        let graph = GraphBuilder::<u32>::new()
+            .vertex_file("v.csr")
+            .edge_file("e.csr")
+            .build_from_iter(edges.into_iter().map(Ok), &destination_folder_name)
+            .unwrap();
+
+        assert_eq!(graph.n_nodes(), 2);
+        assert!(std::path::Path::new(&format!("{}/v.csr", destination_folder_name)).exists());
+        assert!(std::path::Path::new(&format!("{}/e.csr", destination_folder_name)).exists());
+        assert!(!std::path::Path::new(&format!("{}/vertex.csr", destination_folder_name)).exists());
+    }
+}
@@ -1,10 +1,18 @@
 use super::{
     util::{GraphData, ValidGraphType},
+    weighted::WeightedGraph,
     Graph,
 };
 
+use std::{
+    io::Write,
+    ops::{Deref, DerefMut},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
 use atomic::Atomic;
-use rayon::prelude::*;
+use rayon::{prelude::*, ThreadPool};
 
 /// This is the compute abstraction over a graph.
 /// It contains an underlying representation of the data that can support running algorithms over it.
@@ -16,6 +24,9 @@ pub struct ComputeGraph<'a, T, DataType> {
     new_active: Vec<Atomic<bool>>, // which nodes are active in the new iteration
     old_data: Vec<Atomic<DataType>>, // the data of the old iteration
     new_data: Vec<Atomic<DataType>>, // the data of the new iteration
+    executor: Executor,            // where the parallel work runs
+    iteration: usize,              // how many steps were taken since the computation started
+    started_at: Option<Instant>,   // when the computation started, on the first push or pull
 }
 
 impl<'a, T, DataType> ComputeGraph<'a, T, DataType>
@@ -36,9 +47,30 @@ where
             new_data: (0..n_nodes)
                 .map(|_| Atomic::new(DataType::default()))
                 .collect::<Vec<_>>(),
+            executor: Executor::default(),
+            iteration: 0,
+            started_at: None,
         }
     }
 
+    /// Runs all of the parallel work of this graph inside `pool`, instead of the global Rayon thread pool.
+    /// This allows restricting the parallelism, e.g. when several graphs run at the same time.
+    pub fn with_thread_pool(mut self, pool: Arc<ThreadPool>) -> Self {
+        self.executor.pool = Some(pool);
+        self
+    }
+
+    /// Sets whether all of the parallel work runs on a single thread, in a fixed order, so that results are reproducible.
+    /// This is purely a debugging aid, and makes no guarantees on performance.
+    pub fn set_deterministic(&mut self, deterministic: bool) {
+        self.executor.sequential = deterministic.then(|| {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(1)
+                .build()
+                .expect("Failed to create the sequential thread pool")
+        });
+    }
+
     /// Set a single node's activity in the next iteration as `status`.
     #[inline]
     pub fn set_active(&mut self, idx: usize, status: bool) {
@@ -54,23 +86,122 @@ where
     /// Sets all nodes' activity in the next iteration as `status`.
     #[inline]
     pub fn fill_active(&mut self, status: bool) {
-        self.new_active
-            .par_iter_mut()
-            .for_each(|a| a.store(status, atomic::Ordering::Relaxed));
+        self.executor.install(|| {
+            self.new_active
+                .par_iter_mut()
+                .for_each(|a| a.store(status, atomic::Ordering::Relaxed))
+        });
     }
 
     /// Sets all nodes' data in the next iteration as `data`.
     #[inline]
     pub fn fill_data(&mut self, data: DataType) {
-        self.new_data
-            .par_iter_mut()
-            .for_each(|a| a.store(data, atomic::Ordering::Relaxed));
+        self.executor.install(|| {
+            self.new_data
+                .par_iter_mut()
+                .for_each(|a| a.store(data, atomic::Ordering::Relaxed))
+        });
+    }
+
+    /// Sets the data of every node in the next iteration to the value at its index in `data`, in parallel.
+    /// Panics if `data` does not hold exactly one value per node.
+    pub fn set_data_from_slice(&mut self, data: &[DataType]) {
+        assert_eq!(
+            data.len(),
+            self.new_data.len(),
+            "the data must hold one value per node"
+        );
+
+        self.executor.install(|| {
+            self.new_data
+                .par_iter_mut()
+                .zip(data.par_iter())
+                .for_each(|(a, value)| a.store(*value, atomic::Ordering::Relaxed))
+        });
+    }
+
+    /// Sets the data and activity of every node in the next iteration to the result of `f` on its index, in a single parallel pass.
+    /// This replaces separate calls to [fill_data](Self::fill_data), [set_data](Self::set_data) and [set_active](Self::set_active).
+    /// As with those, [step](Self::step) must be called before the first push or pull, so that the values become the last iteration.
+    pub fn initialize_with<F>(&mut self, f: F)
+    where
+        F: Fn(usize) -> (DataType, bool) + Sync,
+    {
+        self.executor.install(|| {
+            self.new_data
+                .par_iter_mut()
+                .zip(self.new_active.par_iter_mut())
+                .enumerate()
+                .for_each(|(idx, (data, active))| {
+                    let (value, status) = f(idx);
+                    data.store(value, atomic::Ordering::Relaxed);
+                    active.store(status, atomic::Ordering::Relaxed);
+                })
+        });
+    }
+
+    /// Replaces the data of every node in the last iteration by the result of `f` on it, e.g. to normalize or clip the results of an algorithm.
+    /// The data of the next iteration is updated as well, so that it keeps following the last iteration as after a [step](Self::step).
+    pub fn map_data<F>(&mut self, f: F)
+    where
+        F: Fn(DataType) -> DataType + Sync,
+    {
+        self.executor.install(|| {
+            self.old_data
+                .par_iter_mut()
+                .zip(self.new_data.par_iter_mut())
+                .for_each(|(old, new)| {
+                    let value = f(old.load(atomic::Ordering::Relaxed));
+                    old.store(value, atomic::Ordering::Relaxed);
+                    new.store(value, atomic::Ordering::Relaxed);
+                })
+        });
+    }
+
+    /// Sets the data of every node that is active in the last iteration to the result of `f` on its index and its data, without
+    /// touching any neighbor, e.g. to apply a local rule. The result is written to the next iteration, and no activity is changed.
+    pub fn apply_to_active<F>(&mut self, f: F)
+    where
+        F: Fn(usize, DataType) -> DataType + Sync,
+    {
+        self.executor.install(|| {
+            self.new_data
+                .par_iter_mut()
+                .enumerate()
+                .filter(|(idx, _)| self.old_active[*idx].load(atomic::Ordering::Relaxed))
+                .for_each(|(idx, data)| {
+                    let value = f(idx, self.old_data[idx].load(atomic::Ordering::Relaxed));
+                    data.store(value, atomic::Ordering::Relaxed);
+                })
+        });
+    }
+
+    /// Resets all nodes' data and activity, in both the last and the next iteration, to their defaults.
+    /// Unlike creating a new `ComputeGraph`, this does not reallocate any of the underlying vectors.
+    pub fn reset(&mut self) {
+        self.fill_active(false);
+        self.fill_data(DataType::default());
+
+        // Swap old and new, so that the old ones are reset as well
+        std::mem::swap(&mut self.old_active, &mut self.new_active);
+        std::mem::swap(&mut self.old_data, &mut self.new_data);
+
+        self.fill_active(false);
+        self.fill_data(DataType::default());
+
+        self.iteration = 0;
+        self.started_at = None;
     }
 
     /// Performs a global iteration step, useful in many algorithms.
     /// The previous status of all nodes is now updated to the new status.
     /// The new status is reset to false.
+    /// Once the computation has started, this also counts as one more [iteration](Self::iteration).
     pub fn step(&mut self) {
+        if self.started_at.is_some() {
+            self.iteration += 1;
+        }
+
         // Swap old and new
         std::mem::swap(&mut self.old_active, &mut self.new_active);
         std::mem::swap(&mut self.old_data, &mut self.new_data);
@@ -79,21 +210,111 @@ where
         self.fill_active(false);
 
         // Set new to the status of old
-        self.new_data
-            .par_iter_mut()
-            .zip(self.old_data.par_iter())
-            .for_each(|(x, y)| {
-                x.store(y.load(atomic::Ordering::Relaxed), atomic::Ordering::Relaxed)
-            });
+        self.executor.install(|| {
+            self.new_data
+                .par_iter_mut()
+                .zip(self.old_data.par_iter())
+                .for_each(|(x, y)| {
+                    x.store(y.load(atomic::Ordering::Relaxed), atomic::Ordering::Relaxed)
+                })
+        });
+    }
+
+    /// Returns how many iterations were completed, i.e. how many times [step](Self::step) was called since the first push or pull.
+    /// The steps used to initialize the data before that are not counted.
+    pub fn iteration(&self) -> usize {
+        self.iteration
+    }
+
+    /// Returns the time elapsed since the first push or pull, or zero if the computation has not started yet.
+    pub fn elapsed(&self) -> Duration {
+        self.started_at
+            .map(|started_at| started_at.elapsed())
+            .unwrap_or_default()
+    }
+
+    /// Marks the computation as started, if it was not already.
+    fn start(&mut self) {
+        self.started_at.get_or_insert_with(Instant::now);
     }
 
     /// Returns how many nodes are active in the last iteration.
     /// This function calculates the value every time, so it is recommended to store its value.
     pub fn n_active(&self) -> usize {
-        self.old_active
-            .par_iter()
-            .filter(|x| x.load(atomic::Ordering::Relaxed))
-            .count()
+        self.executor.install(|| {
+            self.old_active
+                .par_iter()
+                .filter(|x| x.load(atomic::Ordering::Relaxed))
+                .count()
+        })
+    }
+
+    /// Returns whether the data of every node changed by at most `epsilon` between the last iteration and the current one.
+    /// As [step](Self::step) copies the data of the last iteration, this should be called after updating the data and before stepping.
+    pub fn has_converged_within(&self, epsilon: DataType) -> bool
+    where
+        DataType: std::ops::Sub<Output = DataType>,
+    {
+        self.executor.install(|| {
+            self.old_data
+                .par_iter()
+                .zip(self.new_data.par_iter())
+                .all(|(old, new)| {
+                    let old = old.load(atomic::Ordering::Relaxed);
+                    let new = new.load(atomic::Ordering::Relaxed);
+                    let difference = if old > new { old - new } else { new - old };
+                    difference <= epsilon
+                })
+        })
+    }
+
+    /// Combines the data of all nodes in the last iteration with `f`, starting from `identity`.
+    /// `f` is applied in parallel over arbitrary groups of nodes, so it must be associative and `identity` must not change the result.
+    pub fn reduce_data<F>(&self, identity: DataType, f: F) -> DataType
+    where
+        F: Fn(DataType, DataType) -> DataType + Sync + Send,
+    {
+        self.executor.install(|| {
+            self.old_data
+                .par_iter()
+                .map(|x| x.load(atomic::Ordering::Relaxed))
+                .reduce(|| identity, &f)
+        })
+    }
+
+    /// Returns the sum of the data of all nodes in the last iteration.
+    pub fn sum_data(&self) -> DataType
+    where
+        DataType: num_traits::Zero,
+    {
+        self.reduce_data(DataType::zero(), |a, b| a + b)
+    }
+
+    /// Returns the largest data of all nodes in the last iteration, or the minimum value of `DataType` if there are no nodes.
+    pub fn max_data(&self) -> DataType
+    where
+        DataType: num_traits::Bounded,
+    {
+        self.reduce_data(DataType::min_value(), |a, b| if b > a { b } else { a })
+    }
+
+    /// Returns the smallest data of all nodes in the last iteration, or the maximum value of `DataType` if there are no nodes.
+    pub fn min_data(&self) -> DataType
+    where
+        DataType: num_traits::Bounded,
+    {
+        self.reduce_data(DataType::max_value(), |a, b| if b < a { b } else { a })
+    }
+
+    /// Returns the indices of the nodes that are active in the last iteration, in increasing order.
+    pub fn active_nodes(&self) -> Vec<usize> {
+        self.executor.install(|| {
+            self.old_active
+                .par_iter()
+                .enumerate()
+                .filter_map(|(idx, x)| x.load(atomic::Ordering::Relaxed).then_some(idx))
+                .collect()
+        })
     }
 
     /// This function iterates over the active nodes in the last iteration and applies `func` on them.
@@ -102,29 +323,198 @@ where
     where
         F: Fn(DataType, &Atomic<DataType>) -> bool + Sync,
     {
-        self.graph
-            .par_iter()
-            // Compute only those that are active in the last iteration
-            .filter(|(idx, _)| self.old_active[*idx].load(atomic::Ordering::Relaxed))
-            .for_each(|(idx, edges)| {
-                // Update
-                for edge in edges {
-                    // If update yielded improvement then
-                    if func(
-                        self.old_data[idx].load(atomic::Ordering::Relaxed),
-                        &self.new_data[edge.as_()],
-                    ) {
-                        // Mark it as active in the next iteration
-                        self.new_active[edge.as_()].store(true, atomic::Ordering::Relaxed);
+        self.push_with_control(|old, new| func(old, new).into());
+    }
+
+    /// Same as [push](Self::push), except `func` returns a [PushControl], which can also stop going through the
+    /// neighbors of the current node, e.g. once it is known that the node has nothing left to contribute.
+    /// The neighbors of a node are visited in the order they are stored.
+    pub fn push_with_control<F>(&mut self, func: F)
+    where
+        F: Fn(DataType, &Atomic<DataType>) -> PushControl + Sync,
+    {
+        self.start();
+        self.executor.install(|| {
+            self.graph
+                .par_iter()
+                // Compute only those that are active in the last iteration
+                .filter(|(idx, _)| self.old_active[*idx].load(atomic::Ordering::Relaxed))
+                .for_each(|(idx, edges)| {
+                    // Update
+                    for edge in edges {
+                        match func(
+                            self.old_data[idx].load(atomic::Ordering::Relaxed),
+                            &self.new_data[edge.as_()],
+                        ) {
+                            // Mark it as active in the next iteration
+                            PushControl::Continue => {
+                                self.new_active[edge.as_()].store(true, atomic::Ordering::Relaxed)
+                            }
+                            PushControl::Skip => {}
+                            PushControl::StopNode => break,
+                        }
+                    }
+                })
+        });
+    }
+
+    /// Same as [push](Self::push), except `func` also receives the target node of the edge being traversed, as its second argument.
+    pub fn push_with_edges<F>(&mut self, func: F)
+    where
+        F: Fn(DataType, T, &Atomic<DataType>) -> bool + Sync,
+    {
+        self.start();
+        self.executor.install(|| {
+            self.graph
+                .par_iter()
+                .filter(|(idx, _)| self.old_active[*idx].load(atomic::Ordering::Relaxed))
+                .for_each(|(idx, edges)| {
+                    for edge in edges {
+                        if func(
+                            self.old_data[idx].load(atomic::Ordering::Relaxed),
+                            *edge,
+                            &self.new_data[edge.as_()],
+                        ) {
+                            self.new_active[edge.as_()].store(true, atomic::Ordering::Relaxed);
+                        }
+                    }
+                })
+        });
+    }
+
+    /// Same as [push](Self::push), except `func` also receives the position of the edge in the edge array of the graph,
+    /// so that data attached to each edge (such as weights) can be looked up.
+    pub(crate) fn push_with_edge_index<F>(&mut self, func: F)
+    where
+        F: Fn(usize, DataType, &Atomic<DataType>) -> bool + Sync,
+    {
+        self.start();
+        let offsets = self.graph.nodes.get_data_as_slice();
+
+        self.executor.install(|| {
+            self.graph
+                .par_iter()
+                .filter(|(idx, _)| self.old_active[*idx].load(atomic::Ordering::Relaxed))
+                .for_each(|(idx, edges)| {
+                    let first_edge = offsets[idx];
+                    for (offset, edge) in edges.iter().enumerate() {
+                        if func(
+                            first_edge + offset,
+                            self.old_data[idx].load(atomic::Ordering::Relaxed),
+                            &self.new_data[edge.as_()],
+                        ) {
+                            self.new_active[edge.as_()].store(true, atomic::Ordering::Relaxed);
+                        }
                     }
+                })
+        });
+    }
+
+    /// This function iterates over all nodes and lets each of them compute its own new state from its neighbors.
+    /// `func` receives four arguments: the node `idx`, its `neighbors`, the `old` state of every node, and the node's `new` state.
+    /// The node is marked as active in the next iteration if `func` returns true.
+    /// As the graph only stores outgoing edges, pulling along incoming edges requires running it over the transposed graph.
+    pub fn pull<F>(&mut self, func: F)
+    where
+        F: Fn(usize, &[T], &[Atomic<DataType>], &Atomic<DataType>) -> bool + Sync,
+    {
+        self.start();
+        self.executor.install(|| {
+            self.graph.par_iter().for_each(|(idx, edges)| {
+                // If update yielded improvement then
+                if func(idx, edges, &self.old_data, &self.new_data[idx]) {
+                    // Mark it as active in the next iteration
+                    self.new_active[idx].store(true, atomic::Ordering::Relaxed);
                 }
-            });
+            })
+        });
+    }
+
+    /// The first phase of the scatter-gather model: every node that is active in the last iteration calls `func` on each of its edges.
+    /// `func` receives the node `idx`, its `old` state and the edge's target, and returns the message to send to the target, if any.
+    /// Unlike [push](Self::push), nothing is updated here: the messages are returned, grouped by target, to be passed to [gather](Self::gather).
+    pub fn scatter<M, F>(&self, func: F) -> Messages<M>
+    where
+        M: Send,
+        F: Fn(usize, DataType, T) -> Option<M> + Sync,
+    {
+        let func = &func;
+        let messages = self.executor.install(|| {
+            let mut messages = self
+                .graph
+                .par_iter()
+                .filter(|(idx, _)| self.old_active[*idx].load(atomic::Ordering::Relaxed))
+                .flat_map_iter(|(idx, edges)| {
+                    let old = self.old_data[idx].load(atomic::Ordering::Relaxed);
+                    edges
+                        .iter()
+                        .filter_map(move |edge| func(idx, old, *edge).map(|m| (edge.as_(), m)))
+                })
+                .collect::<Vec<_>>();
+
+            // Stable, so messages to the same node keep the order of their sources
+            messages.par_sort_by_key(|(target, _)| *target);
+            messages
+        });
+
+        let mut offsets = vec![0; self.graph.n_nodes() + 1];
+        for (target, _) in messages.iter() {
+            offsets[target + 1] += 1;
+        }
+        for i in 1..offsets.len() {
+            offsets[i] += offsets[i - 1];
+        }
+
+        Messages {
+            offsets,
+            messages: messages.into_iter().map(|(_, m)| m).collect(),
+        }
+    }
+
+    /// The second phase of the scatter-gather model: every node that received messages from [scatter](Self::scatter) calls `func` on them.
+    /// `func` receives the node `idx`, the messages sent to it, and the node's `new` state.
+    /// The node is marked as active in the next iteration if `func` returns true.
+    pub fn gather<M, F>(&mut self, messages: &Messages<M>, func: F)
+    where
+        M: Sync,
+        F: Fn(usize, &[M], &Atomic<DataType>) -> bool + Sync,
+    {
+        self.start();
+        self.executor.install(|| {
+            (0..self.graph.n_nodes())
+                .into_par_iter()
+                .filter(|idx| !messages.get(*idx).is_empty())
+                .for_each(|idx| {
+                    if func(idx, messages.get(idx), &self.new_data[idx]) {
+                        self.new_active[idx].store(true, atomic::Ordering::Relaxed);
+                    }
+                })
+        });
     }
 
     pub fn get_data_as_slice(&self) -> &[Atomic<DataType>] {
         &self.old_data
     }
 
+    /// Returns a copy of the data of every node in the last iteration, loaded in parallel with relaxed ordering.
+    pub fn collect_data(&self) -> Vec<DataType> {
+        self.collect_data_with_ordering(atomic::Ordering::Relaxed)
+    }
+
+    /// Same as [collect_data](Self::collect_data), except each value is loaded with `ordering`.
+    pub fn collect_data_with_ordering(&self, ordering: atomic::Ordering) -> Vec<DataType> {
+        self.executor
+            .install(|| self.old_data.par_iter().map(|x| x.load(ordering)).collect())
+    }
+
+    /// Returns the data being written in the current iteration, i.e. the values that become the result of the next [step](Self::step).
+    /// Between steps these are the values of the last iteration plus every update made since by `push`, `pull` or `gather`.
+    /// WARNING: this is only consistent between calls. While a parallel operation is running, other threads may be updating any value,
+    /// so reads from these atomics can observe partially applied iterations.
+    pub fn get_new_data_as_slice(&self) -> &[Atomic<DataType>] {
+        &self.new_data
+    }
+
     /// Saves the computation's data to the specified file in binary format, following the local machine's endianness.
     pub fn save_data_to_file(&self, filename: &str) -> std::io::Result<()> {
         let mut writer = std::io::BufWriter::new(std::fs::File::create(filename).unwrap());
@@ -135,100 +525,451 @@ where
 
         Ok(())
     }
-}
 
-/// Helper functions for easier atomics.
-pub mod helper {
-    use super::*;
+    /// Saves which nodes are active in the last iteration to the specified file, using one byte (0 or 1) per node.
+    pub fn save_active_to_file(&self, filename: &str) -> std::io::Result<()> {
+        let mut writer = std::io::BufWriter::new(std::fs::File::create(filename)?);
+        for active in self.old_active.iter() {
+            writer.write_all(&[active.load(atomic::Ordering::Relaxed) as u8])?;
+        }
 
-    /// Performs an atomic min function by using atomic operations
-    pub fn atomic_min<T, F>(src_val: T, dst: &Atomic<T>, value: F) -> bool
-    where
-        F: Fn(T) -> T,
-        T: Copy + std::cmp::PartialOrd + std::fmt::Debug,
-    {
-        let mut dst_val = dst.load(atomic::Ordering::Acquire);
-        let mut status = false;
+        writer.flush()
+    }
 
-        while value(src_val) < dst_val && !status {
-            let res = dst.compare_exchange(
-                dst_val,
-                value(src_val),
-                atomic::Ordering::Release,
-                atomic::Ordering::Relaxed,
-            );
+    /// Restores which nodes are active in the last iteration from a file written by [save_active_to_file](Self::save_active_to_file).
+    pub fn load_active_from_file(&mut self, filename: &str) -> std::io::Result<()> {
+        let bytes = std::fs::read(filename)?;
+        if bytes.len() != self.old_active.len() {
+            Err(std::io::ErrorKind::InvalidData)?;
+        }
 
-            match res {
-                Ok(_) => status = true,
-                Err(val) => dst_val = val,
-            }
+        self.executor.install(|| {
+            self.old_active
+                .par_iter_mut()
+                .zip(bytes.par_iter())
+                .for_each(|(active, byte)| active.store(*byte != 0, atomic::Ordering::Relaxed))
+        });
+
+        Ok(())
+    }
+}
+
+/// What [ComputeGraph::push_with_control] does after applying its function on an edge.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PushControl {
+    /// Marks the neighbor as active in the next iteration, and goes on to the next neighbor.
+    Continue,
+    /// Leaves the neighbor's activity untouched, and goes on to the next neighbor.
+    Skip,
+    /// Leaves the neighbor's activity untouched, and skips the remaining neighbors of the node.
+    StopNode,
+}
+
+impl From<bool> for PushControl {
+    /// `true` activates the neighbor as in [ComputeGraph::push], and `false` skips it.
+    fn from(activate: bool) -> Self {
+        if activate {
+            PushControl::Continue
+        } else {
+            PushControl::Skip
         }
-        status
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use byteorder::{NativeEndian, ReadBytesExt};
+/// The compute abstraction over a graph where each node holds a fixed-size vector of `K` values, instead of a single one.
+/// The values of each node are stored next to each other, so that per-node updates stay cache friendly.
+/// It follows the same iteration model as [ComputeGraph].
+pub struct VecComputeGraph<'a, T, DataType, const K: usize> {
+    graph: &'a Graph<'a, T>,
+    old_active: Vec<Atomic<bool>>,
+    new_active: Vec<Atomic<bool>>,
+    old_data: Vec<[Atomic<DataType>; K]>,
+    new_data: Vec<[Atomic<DataType>; K]>,
+    executor: Executor,
+}
 
-    use crate::compute::helper::atomic_min;
+impl<'a, T, DataType, const K: usize> VecComputeGraph<'a, T, DataType, K>
+where
+    T: ValidGraphType + Send + Sync,
+    DataType: GraphData,
+{
+    /// Creates a new graph that can run algorithms over `K` values per node, like [ComputeGraph::new].
+    pub fn new(graph: &'a Graph<'a, T>) -> Self {
+        let n_nodes = graph.n_nodes();
+        Self {
+            graph,
+            old_active: (0..n_nodes).map(|_| Atomic::new(false)).collect::<Vec<_>>(),
+            new_active: (0..n_nodes).map(|_| Atomic::new(false)).collect::<Vec<_>>(),
+            old_data: (0..n_nodes)
+                .map(|_| std::array::from_fn(|_| Atomic::new(DataType::default())))
+                .collect::<Vec<_>>(),
+            new_data: (0..n_nodes)
+                .map(|_| std::array::from_fn(|_| Atomic::new(DataType::default())))
+                .collect::<Vec<_>>(),
+            executor: Executor::default(),
+        }
+    }
 
-    use super::*;
+    /// Runs all of the parallel work of this graph inside `pool`, like [ComputeGraph::with_thread_pool].
+    pub fn with_thread_pool(mut self, pool: Arc<ThreadPool>) -> Self {
+        self.executor.pool = Some(pool);
+        self
+    }
 
-    fn get_basic_graph<'a>() -> Graph<'a, u32> {
-        // Default graph
-        let edges = vec![(0u32, 1u32), (0, 2), (1, 5), (1, 2), (4, 7)];
+    /// Sets whether all of the parallel work runs on a single thread, like [ComputeGraph::set_deterministic].
+    pub fn set_deterministic(&mut self, deterministic: bool) {
+        self.executor.sequential = deterministic.then(|| {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(1)
+                .build()
+                .expect("Failed to create the sequential thread pool")
+        });
+    }
 
-        get_graph(edges)
+    /// Set a single node's activity in the next iteration as `status`.
+    #[inline]
+    pub fn set_active(&mut self, idx: usize, status: bool) {
+        self.new_active[idx].store(status, atomic::Ordering::Relaxed);
     }
 
-    fn get_graph<'a, T>(edge_list: Vec<(T, T)>) -> Graph<'a, T>
-    where
-        T: ValidGraphType,
-    {
-        // Generate random filename
-        let destination_folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+    /// Sets a single node's data in the next iteration as `data`.
+    #[inline]
+    pub fn set_data(&mut self, idx: usize, data: [DataType; K]) {
+        store_all(&self.new_data[idx], &data);
+    }
 
-        Graph::<T>::from_adjacency_list(
-            edge_list
-                .iter()
-                .map(|(src, dst)| Ok((src.clone(), dst.clone()))),
-            destination_folder_name.as_str(),
-        )
-        .unwrap()
+    /// Sets all nodes' activity in the next iteration as `status`.
+    pub fn fill_active(&mut self, status: bool) {
+        self.executor.install(|| {
+            self.new_active
+                .par_iter_mut()
+                .for_each(|a| a.store(status, atomic::Ordering::Relaxed))
+        });
     }
 
-    #[test]
-    fn basic_graph_traversal() {
-        let graph = get_basic_graph();
+    /// Sets all nodes' data in the next iteration as `data`.
+    pub fn fill_data(&mut self, data: [DataType; K]) {
+        self.executor.install(|| {
+            self.new_data
+                .par_iter_mut()
+                .for_each(|a| store_all(a, &data))
+        });
+    }
 
-        let mut compute = ComputeGraph::<u32, u32>::new(&graph);
+    /// Performs a global iteration step, like [ComputeGraph::step].
+    pub fn step(&mut self) {
+        // Swap old and new
+        std::mem::swap(&mut self.old_active, &mut self.new_active);
+        std::mem::swap(&mut self.old_data, &mut self.new_data);
 
-        // Initialize graph
-        // All nodes on, and data is 0
-        compute.fill_active(true);
-        compute.fill_data(0);
-        compute.step();
+        // Reset new
+        self.fill_active(false);
 
-        compute.push(|_, new_res| {
-            new_res.fetch_add(1, atomic::Ordering::Relaxed);
-            true
+        // Set new to the status of old
+        self.executor.install(|| {
+            self.new_data
+                .par_iter_mut()
+                .zip(self.old_data.par_iter())
+                .for_each(|(x, y)| store_all(x, &load_all(y)))
         });
-        compute.step();
+    }
 
-        assert_eq!(
-            &compute
-                .get_data_as_slice()
-                .iter()
-                .map(|x| x.load(atomic::Ordering::Acquire))
-                .collect::<Vec<_>>(),
-            &vec![0, 1, 2, 0, 0, 1, 0, 1]
-        );
+    /// Returns how many nodes are active in the last iteration.
+    pub fn n_active(&self) -> usize {
+        self.executor.install(|| {
+            self.old_active
+                .par_iter()
+                .filter(|x| x.load(atomic::Ordering::Relaxed))
+                .count()
+        })
     }
 
-    #[test]
-    fn filtered_graph_traversal() {
-        let graph = get_basic_graph();
+    /// This function iterates over the active nodes in the last iteration and applies `func` on each of their edges.
+    /// `func` receives two arguments: `old`, which is the last state of the node, and `new`, which is the current state of the neighbor.
+    pub fn push<F>(&mut self, func: F)
+    where
+        F: Fn(&[DataType; K], &[Atomic<DataType>; K]) -> bool + Sync,
+    {
+        self.executor.install(|| {
+            self.graph
+                .par_iter()
+                .filter(|(idx, _)| self.old_active[*idx].load(atomic::Ordering::Relaxed))
+                .for_each(|(idx, edges)| {
+                    let old = load_all(&self.old_data[idx]);
+                    for edge in edges {
+                        if func(&old, &self.new_data[edge.as_()]) {
+                            self.new_active[edge.as_()].store(true, atomic::Ordering::Relaxed);
+                        }
+                    }
+                })
+        });
+    }
+
+    /// Returns the data of `idx` in the last iteration.
+    pub fn get_data(&self, idx: usize) -> [DataType; K] {
+        load_all(&self.old_data[idx])
+    }
+
+    pub fn get_data_as_slice(&self) -> &[[Atomic<DataType>; K]] {
+        &self.old_data
+    }
+}
+
+/// Loads all values of a vector of atomics.
+fn load_all<DataType, const K: usize>(data: &[Atomic<DataType>; K]) -> [DataType; K]
+where
+    DataType: Copy,
+{
+    std::array::from_fn(|k| data[k].load(atomic::Ordering::Relaxed))
+}
+
+/// Stores all `values` into a vector of atomics.
+fn store_all<DataType, const K: usize>(data: &[Atomic<DataType>; K], values: &[DataType; K])
+where
+    DataType: Copy,
+{
+    for (atomic, value) in data.iter().zip(values.iter()) {
+        atomic.store(*value, atomic::Ordering::Relaxed);
+    }
+}
+
+/// Where the parallel work of a [ComputeGraph] or a [VecComputeGraph] runs.
+#[derive(Default)]
+struct Executor {
+    pool: Option<Arc<ThreadPool>>, // the user's pool, or the global pool if none
+    sequential: Option<ThreadPool>, // a single-threaded pool, used instead of any other in deterministic mode
+}
+
+impl Executor {
+    /// Runs `op` inside the pool that should currently be used.
+    fn install<R, OP>(&self, op: OP) -> R
+    where
+        OP: FnOnce() -> R + Send,
+        R: Send,
+    {
+        match (&self.sequential, &self.pool) {
+            (Some(pool), _) => pool.install(op),
+            (None, Some(pool)) => pool.install(op),
+            (None, None) => op(),
+        }
+    }
+}
+
+/// The messages produced by [ComputeGraph::scatter], grouped by the node they are sent to.
+pub struct Messages<M> {
+    offsets: Vec<usize>,
+    messages: Vec<M>,
+}
+
+impl<M> Messages<M> {
+    /// Returns the messages sent to `node`.
+    pub fn get(&self, node: usize) -> &[M] {
+        &self.messages[self.offsets[node]..self.offsets[node + 1]]
+    }
+
+    /// Returns how many messages were sent in total.
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Returns whether no messages were sent.
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+}
+
+/// The compute abstraction over a [WeightedGraph].
+/// It behaves as a [ComputeGraph] over the unweighted graph, with an additional push that receives the weight of each edge.
+pub struct WeightedComputeGraph<'a, T, W, DataType> {
+    compute: ComputeGraph<'a, T, DataType>,
+    weights: &'a [W],
+}
+
+impl<'a, T, W, DataType> WeightedComputeGraph<'a, T, W, DataType>
+where
+    T: ValidGraphType + Send + Sync,
+    W: GraphData,
+    DataType: GraphData,
+{
+    /// Creates a new weighted graph that can run algorithms, like [ComputeGraph::new].
+    pub fn new(graph: &'a WeightedGraph<'a, T, W>) -> Self {
+        Self {
+            compute: ComputeGraph::new(graph.graph()),
+            weights: graph.weights(),
+        }
+    }
+
+    /// Runs all of the parallel work inside `pool`, like [ComputeGraph::with_thread_pool].
+    pub fn with_thread_pool(mut self, pool: Arc<ThreadPool>) -> Self {
+        self.compute = self.compute.with_thread_pool(pool);
+        self
+    }
+
+    /// Same as [push](ComputeGraph::push), except `func` also receives the weight of the edge being traversed, as its second argument.
+    pub fn push_weighted<F>(&mut self, func: F)
+    where
+        F: Fn(DataType, W, &Atomic<DataType>) -> bool + Sync,
+    {
+        let weights = self.weights;
+        self.compute
+            .push_with_edge_index(|edge, src, dst| func(src, weights[edge], dst));
+    }
+}
+
+impl<'a, T, W, DataType> Deref for WeightedComputeGraph<'a, T, W, DataType> {
+    type Target = ComputeGraph<'a, T, DataType>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.compute
+    }
+}
+
+impl<'a, T, W, DataType> DerefMut for WeightedComputeGraph<'a, T, W, DataType> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.compute
+    }
+}
+
+/// Helper functions for easier atomics.
+pub mod helper {
+    use super::*;
+
+    /// Performs an atomic min function by using atomic operations
+    pub fn atomic_min<T, F>(src_val: T, dst: &Atomic<T>, value: F) -> bool
+    where
+        F: Fn(T) -> T,
+        T: Copy + std::cmp::PartialOrd + std::fmt::Debug,
+    {
+        let mut dst_val = dst.load(atomic::Ordering::Acquire);
+        let mut status = false;
+
+        while value(src_val) < dst_val && !status {
+            let res = dst.compare_exchange(
+                dst_val,
+                value(src_val),
+                atomic::Ordering::Release,
+                atomic::Ordering::Relaxed,
+            );
+
+            match res {
+                Ok(_) => status = true,
+                Err(val) => dst_val = val,
+            }
+        }
+        status
+    }
+
+    /// Performs an atomic max function by using atomic operations
+    pub fn atomic_max<T, F>(src_val: T, dst: &Atomic<T>, value: F) -> bool
+    where
+        F: Fn(T) -> T,
+        T: Copy + std::cmp::PartialOrd + std::fmt::Debug,
+    {
+        let mut dst_val = dst.load(atomic::Ordering::Acquire);
+        let mut status = false;
+
+        while value(src_val) > dst_val && !status {
+            let res = dst.compare_exchange(
+                dst_val,
+                value(src_val),
+                atomic::Ordering::Release,
+                atomic::Ordering::Relaxed,
+            );
+
+            match res {
+                Ok(_) => status = true,
+                Err(val) => dst_val = val,
+            }
+        }
+        status
+    }
+
+    /// Performs an atomic addition of `value(src_val)` to `dst`, and returns the previous value stored at `dst`.
+    /// A compare-and-swap loop is used, so that non-integer types such as `f32` and `f64` are supported as well.
+    pub fn atomic_add<T, F>(src_val: T, dst: &Atomic<T>, value: F) -> T
+    where
+        F: Fn(T) -> T,
+        T: Copy + num_traits::Num,
+    {
+        let increment = value(src_val);
+        let mut dst_val = dst.load(atomic::Ordering::Acquire);
+
+        loop {
+            let res = dst.compare_exchange_weak(
+                dst_val,
+                dst_val + increment,
+                atomic::Ordering::Release,
+                atomic::Ordering::Relaxed,
+            );
+
+            match res {
+                Ok(val) => return val,
+                Err(val) => dst_val = val,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use byteorder::{NativeEndian, ReadBytesExt};
+
+    use crate::compute::helper::{atomic_add, atomic_max, atomic_min};
+
+    use super::*;
+
+    fn get_basic_graph<'a>() -> Graph<'a, u32> {
+        // Default graph
+        let edges = vec![(0u32, 1u32), (0, 2), (1, 5), (1, 2), (4, 7)];
+
+        get_graph(edges)
+    }
+
+    fn get_graph<'a, T>(edge_list: Vec<(T, T)>) -> Graph<'a, T>
+    where
+        T: ValidGraphType,
+    {
+        // Generate random filename
+        let destination_folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+
+        Graph::<T>::from_adjacency_list(
+            edge_list.iter().map(|(src, dst)| Ok((*src, *dst))),
+            destination_folder_name.as_str(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn basic_graph_traversal() {
+        let graph = get_basic_graph();
+
+        let mut compute = ComputeGraph::<u32, u32>::new(&graph);
+
+        // Initialize graph
+        // All nodes on, and data is 0
+        compute.fill_active(true);
+        compute.fill_data(0);
+        compute.step();
+
+        compute.push(|_, new_res| {
+            new_res.fetch_add(1, atomic::Ordering::Relaxed);
+            true
+        });
+        compute.step();
+
+        assert_eq!(
+            &compute
+                .get_data_as_slice()
+                .iter()
+                .map(|x| x.load(atomic::Ordering::Acquire))
+                .collect::<Vec<_>>(),
+            &vec![0, 1, 2, 0, 0, 1, 0, 1]
+        );
+    }
+
+    #[test]
+    fn filtered_graph_traversal() {
+        let graph = get_basic_graph();
 
         let mut compute = ComputeGraph::<u32, u32>::new(&graph);
 
@@ -257,6 +998,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn active_frontier() {
+        let graph = get_basic_graph();
+        let mut compute = ComputeGraph::<u32, u32>::new(&graph);
+
+        for id in 0..graph.n_nodes() {
+            compute.set_active(id, id % 2 == 0);
+        }
+        compute.step();
+
+        assert_eq!(compute.active_nodes(), vec![0, 2, 4, 6]);
+        assert_eq!(compute.active_nodes().len(), compute.n_active());
+    }
+
     #[test]
     fn bfs_disconnected() {
         let graph = get_basic_graph();
@@ -330,6 +1085,89 @@ mod tests {
         );
     }
 
+    #[test]
+    fn bfs_pull_cycle() {
+        // Each node points to its predecessor in the cycle, so pulling follows the cycle forward
+        let edges = vec![
+            (0u32, 7u32),
+            (1, 0),
+            (2, 1),
+            (3, 2),
+            (4, 3),
+            (5, 4),
+            (6, 5),
+            (7, 6),
+        ];
+
+        let graph = get_graph(edges);
+
+        let mut compute = ComputeGraph::<u32, u32>::new(&graph);
+
+        // Initialize the graph
+        // Only the source is active, and data is u32::MAX
+        compute.fill_data(u32::MAX);
+        compute.set_active(0, true);
+        compute.set_data(0, 0);
+        compute.step();
+
+        while compute.n_active() > 0 {
+            compute.pull(|_, neighbors, old, new| {
+                neighbors.iter().fold(false, |status, neighbor| {
+                    let local = old[*neighbor as usize].load(atomic::Ordering::Relaxed);
+                    status | (local != u32::MAX && atomic_min(local, new, |v| v + 1))
+                })
+            });
+            compute.step();
+        }
+
+        assert_eq!(
+            &compute
+                .get_data_as_slice()
+                .iter()
+                .map(|x| x.load(atomic::Ordering::Acquire))
+                .collect::<Vec<_>>(),
+            &vec![0, 1, 2, 3, 4, 5, 6, 7]
+        );
+    }
+
+    #[test]
+    fn reset_and_rerun() {
+        let graph = get_basic_graph();
+
+        let mut compute = ComputeGraph::<u32, u32>::new(&graph);
+
+        for _ in 0..2 {
+            compute.fill_data(u32::MAX);
+            compute.set_active(0, true);
+            compute.set_data(0, 0);
+            compute.step();
+
+            while compute.n_active() > 0 {
+                compute.push(|local, res| atomic_min(local, res, |v| v + 1));
+                compute.step();
+            }
+
+            assert_eq!(
+                &compute
+                    .get_data_as_slice()
+                    .iter()
+                    .map(|x| x.load(atomic::Ordering::Acquire))
+                    .collect::<Vec<_>>(),
+                &vec![0, 1, 1, u32::MAX, u32::MAX, 2, u32::MAX, u32::MAX]
+            );
+
+            compute.set_active(3, true);
+            compute.reset();
+            compute.step();
+
+            assert_eq!(compute.n_active(), 0);
+            assert!(compute
+                .get_data_as_slice()
+                .iter()
+                .all(|x| x.load(atomic::Ordering::Acquire) == 0));
+        }
+    }
+
     #[test]
     fn wcc() {
         let graph = get_basic_graph();
@@ -360,23 +1198,633 @@ mod tests {
     }
 
     #[test]
-    fn save_file() {
-        let graph = get_basic_graph();
-        let mut compute = ComputeGraph::<u32, u32>::new(&graph);
+    fn atomic_min_max() {
+        let value = Atomic::new(5u32);
 
-        for id in 0..graph.n_nodes() {
-            compute.set_data(id, id as u32);
-        }
-        compute.step();
+        assert!(!atomic_min(7, &value, |v| v));
+        assert!(atomic_min(3, &value, |v| v));
+        assert!(!atomic_min(2, &value, |v| v + 1));
+        assert_eq!(value.load(atomic::Ordering::Acquire), 3);
 
-        let output = format!("/tmp/output_{}", rand::random::<u32>());
+        assert!(!atomic_max(1, &value, |v| v));
+        assert!(atomic_max(8, &value, |v| v));
+        assert!(!atomic_max(9, &value, |v| v - 1));
+        assert_eq!(value.load(atomic::Ordering::Acquire), 8);
+    }
 
-        compute.save_data_to_file(&output).unwrap();
+    #[test]
+    fn parallel_accumulation() {
+        let int_sum = Atomic::new(0u64);
+        let float_sum = Atomic::new(0f64);
 
-        let mut rdr = std::io::BufReader::new(std::fs::File::open(&output).unwrap());
+        (0..1000u64).into_par_iter().for_each(|i| {
+            atomic_add(i, &int_sum, |v| v);
+            atomic_add(i as f64, &float_sum, |v| v / 2.0);
+        });
 
-        for i in 0..graph.n_nodes() {
-            assert_eq!(i as u32, rdr.read_u32::<NativeEndian>().unwrap());
+        assert_eq!(int_sum.load(atomic::Ordering::Acquire), 499500);
+        assert_eq!(float_sum.load(atomic::Ordering::Acquire), 249750.0);
+        assert_eq!(atomic_add(1, &int_sum, |v| v), 499500);
+    }
+
+    #[test]
+    fn in_degree_count() {
+        let graph = get_basic_graph();
+
+        let mut compute = ComputeGraph::<u32, u32>::new(&graph);
+
+        compute.fill_active(true);
+        compute.fill_data(0);
+        compute.step();
+
+        compute.push(|_, res| {
+            atomic_add(1, res, |v| v);
+            false
+        });
+        compute.step();
+
+        assert_eq!(
+            &compute
+                .get_data_as_slice()
+                .iter()
+                .map(|x| x.load(atomic::Ordering::Acquire))
+                .collect::<Vec<_>>(),
+            &vec![0, 1, 2, 0, 0, 1, 0, 1]
+        );
+    }
+
+    #[test]
+    fn new_data_before_step() {
+        let graph = get_basic_graph();
+
+        let mut compute = ComputeGraph::<u32, u32>::new(&graph);
+
+        compute.fill_active(true);
+        compute.fill_data(0);
+        compute.step();
+
+        compute.push(|_, res| {
+            atomic_add(1, res, |v| v);
+            false
+        });
+
+        let load = |data: &[Atomic<u32>]| {
+            data.iter()
+                .map(|x| x.load(atomic::Ordering::Acquire))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(load(compute.get_data_as_slice()), vec![0; 8]);
+        assert_eq!(
+            load(compute.get_new_data_as_slice()),
+            vec![0, 1, 2, 0, 0, 1, 0, 1]
+        );
+
+        compute.step();
+        assert_eq!(
+            load(compute.get_data_as_slice()),
+            load(compute.get_new_data_as_slice())
+        );
+    }
+
+    #[test]
+    fn push_with_control() {
+        let graph = get_graph(vec![(0u32, 1u32), (0, 2), (0, 3), (0, 4), (1, 0)]);
+
+        let mut compute = ComputeGraph::<u32, u32>::new(&graph);
+        compute.set_active(0, true);
+        for i in 0..graph.n_nodes() {
+            compute.set_data(i, i as u32 * 10);
+        }
+        compute.step();
+
+        // Neighbor 2 is skipped, and the neighbors after 3 are never visited
+        compute.push_with_control(|_, new| match new.load(atomic::Ordering::Relaxed) / 10 {
+            2 => PushControl::Skip,
+            3 => PushControl::StopNode,
+            _ => {
+                atomic_add(1, new, |v| v);
+                PushControl::Continue
+            }
+        });
+        compute.step();
+
+        assert_eq!(compute.active_nodes(), vec![1]);
+        assert_eq!(
+            compute
+                .get_data_as_slice()
+                .iter()
+                .map(|x| x.load(atomic::Ordering::Relaxed))
+                .collect::<Vec<_>>(),
+            vec![0, 11, 20, 30, 40]
+        );
+    }
+
+    #[test]
+    fn max_propagation() {
+        let graph = get_basic_graph();
+
+        let mut compute = ComputeGraph::<u32, u32>::new(&graph);
+
+        // Initialize the graph
+        // All nodes are ON, and data is the reversed node id
+        compute.fill_active(true);
+        for id in 0..graph.n_nodes() {
+            compute.set_data(id, (graph.n_nodes() - 1 - id) as u32);
+        }
+        compute.step();
+
+        while compute.n_active() > 0 {
+            compute.push(|local, res| atomic_max(local, res, |v| v));
+            compute.step();
+        }
+
+        assert_eq!(
+            &compute
+                .get_data_as_slice()
+                .iter()
+                .map(|x| x.load(atomic::Ordering::Acquire))
+                .collect::<Vec<_>>(),
+            &vec![7, 7, 7, 4, 3, 7, 1, 3]
+        );
+    }
+
+    #[test]
+    fn save_file() {
+        let graph = get_basic_graph();
+        let mut compute = ComputeGraph::<u32, u32>::new(&graph);
+
+        for id in 0..graph.n_nodes() {
+            compute.set_data(id, id as u32);
+        }
+        compute.step();
+
+        let output = format!("/tmp/output_{}", rand::random::<u32>());
+
+        compute.save_data_to_file(&output).unwrap();
+
+        let mut rdr = std::io::BufReader::new(std::fs::File::open(&output).unwrap());
+
+        for i in 0..graph.n_nodes() {
+            assert_eq!(i as u32, rdr.read_u32::<NativeEndian>().unwrap());
+        }
+    }
+
+    #[test]
+    fn save_and_load_active() {
+        let graph = get_basic_graph();
+        let mut compute = ComputeGraph::<u32, u32>::new(&graph);
+
+        for id in 0..graph.n_nodes() {
+            compute.set_active(id, id % 3 == 0);
+        }
+        compute.step();
+
+        let output = format!("/tmp/output_{}", rand::random::<u32>());
+        compute.save_active_to_file(&output).unwrap();
+
+        assert_eq!(
+            std::fs::read(&output).unwrap(),
+            vec![1u8, 0, 0, 1, 0, 0, 1, 0]
+        );
+
+        let mut restored = ComputeGraph::<u32, u32>::new(&graph);
+        restored.load_active_from_file(&output).unwrap();
+        assert_eq!(restored.active_nodes(), vec![0, 3, 6]);
+    }
+
+    #[test]
+    fn push_with_edge_targets() {
+        let graph = get_basic_graph();
+        let mut compute = ComputeGraph::<u32, u32>::new(&graph);
+
+        compute.fill_active(true);
+        compute.step();
+
+        // Every node reached by an edge stores its own id
+        compute.push_with_edges(|local, target, res| atomic_max(local, res, |v| v + target));
+        compute.step();
+
+        assert_eq!(
+            compute
+                .get_data_as_slice()
+                .iter()
+                .map(|x| x.load(atomic::Ordering::Acquire))
+                .collect::<Vec<_>>(),
+            vec![0, 1, 2, 0, 0, 5, 0, 7]
+        );
+    }
+
+    #[test]
+    fn push_weighted() {
+        let destination_folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+        let graph = WeightedGraph::<u32, u32>::from_weighted_adjacency_list(
+            vec![(0u32, 1u32, 3u32), (0, 2, 5), (1, 2, 1)]
+                .into_iter()
+                .map(Ok),
+            &destination_folder_name,
+        )
+        .unwrap();
+        let mut compute = WeightedComputeGraph::<u32, u32, u32>::new(&graph);
+
+        compute.fill_active(true);
+        compute.step();
+
+        // Each node stores the heaviest edge reaching it
+        compute.push_weighted(|_, weight, res| atomic_max(weight, res, |v| v));
+        compute.step();
+
+        assert_eq!(
+            compute
+                .get_data_as_slice()
+                .iter()
+                .map(|x| x.load(atomic::Ordering::Acquire))
+                .collect::<Vec<_>>(),
+            vec![0, 3, 5]
+        );
+    }
+
+    #[test]
+    fn converged_within() {
+        let graph = get_graph(vec![(0u32, 1u32), (1, 0)]);
+        let mut compute = ComputeGraph::<u32, f64>::new(&graph);
+
+        compute.fill_data(1.0);
+        compute.fill_active(true);
+        compute.step();
+        assert!(compute.has_converged_within(0.0));
+
+        // Halve the distance to 2.0 in every iteration
+        let mut iterations = 0;
+        loop {
+            compute.pull(|idx, _, old, new| {
+                let value = old[idx].load(atomic::Ordering::Relaxed);
+                new.store((value + 2.0) / 2.0, atomic::Ordering::Relaxed);
+                true
+            });
+            iterations += 1;
+
+            if compute.has_converged_within(1e-3) {
+                break;
+            }
+            compute.step();
+        }
+
+        assert_eq!(iterations, 10);
+        assert!(!compute.has_converged_within(1e-4));
+    }
+
+    #[test]
+    fn collect_data() {
+        let graph = get_graph(vec![(0u32, 1u32), (1, 2)]);
+        let mut compute = ComputeGraph::<u32, u32>::new(&graph);
+
+        compute.set_data_from_slice(&[3, 1, 2]);
+        assert_eq!(compute.collect_data(), vec![0, 0, 0]);
+
+        compute.step();
+        assert_eq!(compute.collect_data(), vec![3, 1, 2]);
+        assert_eq!(
+            compute.collect_data_with_ordering(atomic::Ordering::SeqCst),
+            vec![3, 1, 2]
+        );
+    }
+
+    #[test]
+    fn set_data_from_slice() {
+        let graph = get_graph(vec![(0u32, 1u32), (1, 2)]);
+        let mut compute = ComputeGraph::<u32, f64>::new(&graph);
+
+        compute.set_data_from_slice(&[0.5, 1.5, 2.5]);
+        compute.step();
+
+        assert_eq!(
+            compute
+                .get_data_as_slice()
+                .iter()
+                .map(|x| x.load(atomic::Ordering::Relaxed))
+                .collect::<Vec<_>>(),
+            vec![0.5, 1.5, 2.5]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "the data must hold one value per node")]
+    fn set_data_from_short_slice() {
+        let graph = get_graph(vec![(0u32, 1u32), (1, 2)]);
+        let mut compute = ComputeGraph::<u32, f64>::new(&graph);
+
+        compute.set_data_from_slice(&[0.5, 1.5]);
+    }
+
+    #[test]
+    fn initialize_with() {
+        let graph = get_graph(vec![(0u32, 1u32), (1, 2), (2, 3), (4, 3)]);
+        let mut compute = ComputeGraph::<u32, u32>::new(&graph);
+
+        // Two sources, 0 and 4
+        compute.initialize_with(|idx| {
+            if idx % 4 == 0 {
+                (0, true)
+            } else {
+                (u32::MAX, false)
+            }
+        });
+        compute.step();
+        assert_eq!(compute.active_nodes(), vec![0, 4]);
+
+        while compute.n_active() > 0 {
+            compute.push(|local, res| atomic_min(local, res, |v| v + 1));
+            compute.step();
         }
+
+        assert_eq!(
+            compute
+                .get_data_as_slice()
+                .iter()
+                .map(|x| x.load(atomic::Ordering::Relaxed))
+                .collect::<Vec<_>>(),
+            vec![0, 1, 2, 1, 0]
+        );
+    }
+
+    #[test]
+    fn apply_to_active() {
+        let graph = get_graph(vec![(0u32, 1u32), (1, 2), (2, 3)]);
+        let mut compute = ComputeGraph::<u32, u32>::new(&graph);
+
+        compute.initialize_with(|idx| (idx as u32 * 10, idx % 2 == 0));
+        compute.step();
+
+        compute.apply_to_active(|idx, data| data + idx as u32);
+        compute.step();
+
+        assert_eq!(
+            compute
+                .get_data_as_slice()
+                .iter()
+                .map(|x| x.load(atomic::Ordering::Relaxed))
+                .collect::<Vec<_>>(),
+            vec![0, 10, 22, 30]
+        );
+        // Nothing was activated
+        assert_eq!(compute.n_active(), 0);
+    }
+
+    #[test]
+    fn map_data() {
+        let graph = get_graph(vec![(0u32, 1u32), (1, 2), (2, 0)]);
+        let mut compute = ComputeGraph::<u32, f64>::new(&graph);
+
+        compute.set_data(0, 1.0);
+        compute.set_data(1, 3.0);
+        compute.set_data(2, 4.0);
+        compute.step();
+
+        // Normalize by the sum
+        let sum = compute
+            .get_data_as_slice()
+            .iter()
+            .map(|x| x.load(atomic::Ordering::Relaxed))
+            .sum::<f64>();
+        compute.map_data(|x| x / sum);
+
+        let data = |slice: &[Atomic<f64>]| {
+            slice
+                .iter()
+                .map(|x| x.load(atomic::Ordering::Relaxed))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(data(compute.get_data_as_slice()), vec![0.125, 0.375, 0.5]);
+        assert_eq!(
+            data(compute.get_new_data_as_slice()),
+            vec![0.125, 0.375, 0.5]
+        );
+
+        // Survives the next step
+        compute.step();
+        assert_eq!(data(compute.get_data_as_slice()), vec![0.125, 0.375, 0.5]);
+    }
+
+    #[test]
+    fn reduce_data() {
+        let graph = get_graph(vec![(0u32, 1u32), (1, 2), (2, 3)]);
+        let mut compute = ComputeGraph::<u32, u32>::new(&graph);
+
+        compute.set_data(0, 5);
+        compute.set_data(1, 2);
+        compute.set_data(2, 9);
+        compute.set_data(3, 4);
+
+        // Only the last iteration is considered
+        assert_eq!(compute.sum_data(), 0);
+        compute.step();
+
+        assert_eq!(compute.sum_data(), 20);
+        assert_eq!(compute.max_data(), 9);
+        assert_eq!(compute.min_data(), 2);
+        assert_eq!(compute.reduce_data(0, |a, b| a | b), 15);
+    }
+
+    #[test]
+    fn scatter_gather() {
+        let graph = get_basic_graph();
+        let mut compute = ComputeGraph::<u32, u32>::new(&graph);
+
+        compute.fill_active(true);
+        compute.step();
+
+        // Every node sends its id to its neighbors, except to node 5
+        let messages = compute.scatter(|idx, _, target| (target != 5).then_some(idx));
+        assert_eq!(messages.len(), 4);
+        assert_eq!(messages.get(2), &[0, 1]);
+        assert!(messages.get(5).is_empty());
+
+        // Each node stores the sum of the ids it received
+        compute.gather(&messages, |_, received, new| {
+            new.store(
+                received.iter().sum::<usize>() as u32,
+                atomic::Ordering::Relaxed,
+            );
+            true
+        });
+        compute.step();
+
+        assert_eq!(
+            compute
+                .get_data_as_slice()
+                .iter()
+                .map(|x| x.load(atomic::Ordering::Acquire))
+                .collect::<Vec<_>>(),
+            vec![0, 0, 1, 0, 0, 0, 0, 4]
+        );
+        assert_eq!(compute.active_nodes(), vec![1, 2, 7]);
+    }
+
+    #[test]
+    fn custom_thread_pool() {
+        let graph = get_basic_graph();
+        let pool = Arc::new(
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(2)
+                .build()
+                .unwrap(),
+        );
+        let mut compute = ComputeGraph::<u32, u32>::new(&graph).with_thread_pool(pool);
+
+        compute.fill_active(false);
+        compute.fill_data(u32::MAX);
+        compute.set_active(0, true);
+        compute.set_data(0, 0);
+        compute.step();
+
+        // The closure runs on one of the threads of the pool
+        while compute.n_active() > 0 {
+            compute.push(|src, dst| {
+                assert!(rayon::current_thread_index().unwrap() < 2);
+                atomic_min(src, dst, |v| v + 1)
+            });
+            compute.step();
+        }
+
+        assert_eq!(
+            compute
+                .get_data_as_slice()
+                .iter()
+                .map(|x| x.load(atomic::Ordering::Acquire))
+                .collect::<Vec<_>>(),
+            vec![0, 1, 1, u32::MAX, u32::MAX, 2, u32::MAX, u32::MAX]
+        );
+    }
+
+    #[test]
+    fn deterministic_mode() {
+        let graph = get_basic_graph();
+        let mut compute = ComputeGraph::<u32, u32>::new(&graph);
+        compute.set_deterministic(true);
+
+        for id in 0..graph.n_nodes() {
+            compute.set_data(id, (graph.n_nodes() - 1 - id) as u32);
+        }
+        compute.fill_active(true);
+        compute.step();
+
+        // The edges are visited in order, as if the graph was iterated sequentially
+        let order = std::sync::Mutex::new(Vec::new());
+        compute.push(|src, dst| {
+            order.lock().unwrap().push(src);
+            atomic_max(src, dst, |v| v)
+        });
+        compute.step();
+
+        assert_eq!(order.into_inner().unwrap(), vec![7, 7, 6, 6, 3]);
+        assert_eq!(compute.active_nodes(), vec![1, 2, 5, 7]);
+
+        compute.set_deterministic(false);
+        assert_eq!(compute.n_active(), 4);
+    }
+
+    #[test]
+    fn iteration_and_elapsed() {
+        let graph = get_basic_graph();
+        let mut compute = ComputeGraph::<u32, u32>::new(&graph);
+
+        compute.fill_data(u32::MAX);
+        compute.set_active(0, true);
+        compute.set_data(0, 0);
+        compute.step(); // Set data
+
+        assert_eq!(compute.iteration(), 0);
+        assert_eq!(compute.elapsed(), Duration::ZERO);
+
+        while compute.n_active() > 0 {
+            compute.push(|src, dst| atomic_min(src, dst, |v| v + 1));
+            compute.step();
+        }
+
+        // 0 -> {1, 2} -> 5, plus the last iteration that found nothing new
+        assert_eq!(compute.iteration(), 3);
+        assert!(compute.elapsed() > Duration::ZERO);
+
+        compute.reset();
+        assert_eq!(compute.iteration(), 0);
+    }
+
+    #[test]
+    fn vec_bfs_two_sources() {
+        let graph = get_basic_graph();
+        let mut compute = VecComputeGraph::<u32, u32, 2>::new(&graph);
+
+        // Distances from node 0 and from node 1 at the same time
+        compute.fill_data([u32::MAX, u32::MAX]);
+        compute.set_data(0, [0, u32::MAX]);
+        compute.set_data(1, [u32::MAX, 0]);
+        compute.set_active(0, true);
+        compute.set_active(1, true);
+        compute.step();
+
+        while compute.n_active() > 0 {
+            compute.push(|src, dst| {
+                src.iter()
+                    .zip(dst.iter())
+                    .fold(false, |status, (src, dst)| {
+                        atomic_min(*src, dst, |v| v.saturating_add(1)) || status
+                    })
+            });
+            compute.step();
+        }
+
+        let max = u32::MAX;
+        assert_eq!(
+            (0..graph.n_nodes())
+                .map(|i| compute.get_data(i))
+                .collect::<Vec<_>>(),
+            vec![
+                [0, max],
+                [1, 0],
+                [1, 1],
+                [max, max],
+                [max, max],
+                [2, 1],
+                [max, max],
+                [max, max]
+            ]
+        );
+    }
+
+    #[test]
+    fn vec_thread_pool_and_deterministic_mode() {
+        let graph = get_basic_graph();
+        let pool = Arc::new(
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(2)
+                .build()
+                .unwrap(),
+        );
+        let mut compute = VecComputeGraph::<u32, u32, 1>::new(&graph).with_thread_pool(pool);
+
+        compute.fill_active(true);
+        compute.step();
+
+        // The closure runs on one of the threads of the pool
+        compute.push(|_, _| {
+            assert!(rayon::current_thread_index().unwrap() < 2);
+            false
+        });
+
+        // The edges are visited in order, as if the graph was iterated sequentially
+        compute.set_deterministic(true);
+        for id in 0..graph.n_nodes() {
+            compute.set_data(id, [id as u32]);
+        }
+        compute.fill_active(true);
+        compute.step();
+
+        let order = std::sync::Mutex::new(Vec::new());
+        compute.push(|src, _| {
+            assert_eq!(rayon::current_num_threads(), 1);
+            order.lock().unwrap().push(src[0]);
+            false
+        });
+        assert_eq!(order.into_inner().unwrap(), vec![0, 0, 1, 1, 4]);
     }
 }
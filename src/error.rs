@@ -0,0 +1,104 @@
+use std::fmt;
+
+/// The errors that can occur while constructing, loading or validating a graph.
+/// Functions that return `std::io::Error` wrap these as their inner error, so they can be recovered with `downcast_ref`.
+#[derive(Debug)]
+pub enum GraphError {
+    /// An underlying I/O operation failed.
+    Io(std::io::Error),
+    /// The metadata sidecar of a graph folder does not match the graph files it describes.
+    MetadataMismatch {
+        field: &'static str,
+        expected: String,
+        found: String,
+    },
+    /// The CSR arrays of a graph break one of their invariants, e.g. because a file was truncated or corrupted.
+    CorruptedCsr(&'static str),
+    /// A file of a graph folder could not be opened, e.g. because the folder does not exist or due to its permissions.
+    FileAccess {
+        path: String,
+        source: std::io::Error,
+    },
+    /// The folder where a graph was going to be constructed already exists, and the [GraphBuilder](crate::builder::GraphBuilder)
+    /// was told not to reuse it with [OverwritePolicy::Fail](crate::builder::OverwritePolicy::Fail).
+    /// Only the builder checks this: the `from_*` constructors of [Graph](crate::Graph) always reuse existing folders.
+    DestinationExists(String),
+    /// An edge list that must be sorted by source has an edge, on the 1-indexed `line` with the text `content`,
+    /// whose source is lower than the source of the edge before it.
+    UnsortedEdge {
+        line: usize,
+        content: String,
+        previous_source: String,
+    },
+}
+
+impl fmt::Display for GraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphError::Io(e) => write!(f, "{}", e),
+            GraphError::MetadataMismatch {
+                field,
+                expected,
+                found,
+            } => write!(
+                f,
+                "metadata mismatch on `{}`: expected {}, found {}",
+                field, expected, found
+            ),
+            GraphError::CorruptedCsr(reason) => write!(f, "corrupted CSR: {}", reason),
+            GraphError::FileAccess { path, source } => {
+                write!(f, "failed to open `{}`: {}", path, source)
+            }
+            GraphError::DestinationExists(path) => write!(
+                f,
+                "destination `{}` already exists, and the graph builder was told not to reuse it",
+                path
+            ),
+            GraphError::UnsortedEdge {
+                line,
+                content,
+                previous_source,
+            } => write!(
+                f,
+                "line {}: edge `{}` comes after an edge from {}",
+                line, content, previous_source
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GraphError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            GraphError::Io(e) => Some(e),
+            GraphError::FileAccess { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for GraphError {
+    fn from(e: std::io::Error) -> Self {
+        GraphError::Io(e)
+    }
+}
+
+impl From<std::io::ErrorKind> for GraphError {
+    fn from(kind: std::io::ErrorKind) -> Self {
+        GraphError::Io(kind.into())
+    }
+}
+
+impl From<GraphError> for std::io::Error {
+    fn from(e: GraphError) -> Self {
+        match e {
+            GraphError::Io(e) => e,
+            // Keep the kind of the failure, so that e.g. a missing file is still `NotFound`
+            GraphError::FileAccess { ref source, .. } => std::io::Error::new(source.kind(), e),
+            GraphError::DestinationExists(_) => {
+                std::io::Error::new(std::io::ErrorKind::AlreadyExists, e)
+            }
+            e => std::io::Error::new(std::io::ErrorKind::InvalidData, e),
+        }
+    }
+}
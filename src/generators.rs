@@ -0,0 +1,249 @@
+use rand::Rng;
+
+use super::{util::ValidGraphType, Graph};
+
+/// Generates a directed G(n, p) random graph with `n` nodes, where each of the `n * (n - 1)` possible edges between
+/// distinct nodes exists independently with probability `p`, and stores it in `folder_name`.
+/// Instead of drawing every possible edge, the gap to the next edge is drawn from its geometric distribution,
+/// so the time is proportional to the number of edges generated.
+/// Fails with `InvalidData` if `p` is not in `0..=1`, or if `n` is 0, since a graph holds at least one node.
+pub fn erdos_renyi<N>(
+    n: usize,
+    p: f64,
+    rng: &mut impl Rng,
+    folder_name: &str,
+) -> Result<Graph<'static, N>, std::io::Error>
+where
+    N: ValidGraphType,
+{
+    if !(0.0..=1.0).contains(&p) || n == 0 {
+        Err(std::io::ErrorKind::InvalidData)?;
+    }
+
+    // The possible edges are numbered by source, and then by destination skipping the source itself
+    let n_possible = n * n.saturating_sub(1);
+    // Computed as ln(1 + x), since 1 - p rounds to 1 for tiny values of p
+    let log_q = (-p).ln_1p();
+    let mut next = 0usize;
+    let mut first = true;
+
+    let edges = std::iter::from_fn(move || {
+        let gap = if p == 1.0 {
+            0
+        } else if p == 0.0 {
+            n_possible
+        } else {
+            // Number of edges skipped before the next one, following a geometric distribution
+            ((1.0 - rng.gen::<f64>()).ln() / log_q).floor() as usize
+        };
+        next = next.saturating_add(gap).saturating_add(!first as usize);
+        first = false;
+
+        (next < n_possible).then(|| {
+            let src = next / (n - 1);
+            let column = next % (n - 1);
+            let dst = if column < src { column } else { column + 1 };
+            (src, dst)
+        })
+    })
+    .map(|(src, dst)| {
+        N::from_usize(src)
+            .zip(N::from_usize(dst))
+            .ok_or_else(|| std::io::ErrorKind::InvalidData.into())
+    });
+
+    Graph::from_sorted_edges(edges, n, folder_name)
+}
+
+/// Generates an undirected scale-free graph with `n` nodes by preferential attachment, and stores it in `folder_name`.
+/// The graph starts as a complete graph on the first `m + 1` nodes, and every further node connects to `m` distinct
+/// earlier nodes, each picked with probability proportional to its current degree. Both directions of each edge are stored.
+/// Fails with `InvalidData` if `m` is 0 or if `n` is not larger than `m`.
+pub fn barabasi_albert<N>(
+    n: usize,
+    m: usize,
+    rng: &mut impl Rng,
+    folder_name: &str,
+) -> Result<Graph<'static, N>, std::io::Error>
+where
+    N: ValidGraphType,
+{
+    if m == 0 || n <= m {
+        Err(std::io::ErrorKind::InvalidData)?;
+    }
+
+    let mut edges = Vec::with_capacity(2 * m * n);
+    for a in 0..=m {
+        for b in 0..a {
+            edges.push((a, b));
+        }
+    }
+
+    // Every node appears once per edge it has, so a uniform pick from here is proportional to the degree
+    let mut endpoints = edges.iter().flat_map(|&(a, b)| [a, b]).collect::<Vec<_>>();
+    let mut targets = Vec::with_capacity(m);
+    for node in m + 1..n {
+        targets.clear();
+        while targets.len() < m {
+            let target = endpoints[rng.gen_range(0..endpoints.len())];
+            if !targets.contains(&target) {
+                targets.push(target);
+            }
+        }
+
+        for target in targets.iter() {
+            edges.push((node, *target));
+            endpoints.extend([node, *target]);
+        }
+    }
+
+    let mut edges = edges
+        .into_iter()
+        .flat_map(|(a, b)| [(a, b), (b, a)])
+        .collect::<Vec<_>>();
+    edges.sort_unstable();
+
+    let edges = edges.into_iter().map(|(src, dst)| {
+        N::from_usize(src)
+            .zip(N::from_usize(dst))
+            .ok_or_else(|| std::io::ErrorKind::InvalidData.into())
+    });
+
+    Graph::from_sorted_edges(edges, n, folder_name)
+}
+
+/// Generates a grid with `rows` rows and `cols` columns, where the node in row `r` and column `c` is `r * cols + c`,
+/// and stores it in `folder_name`. Each node is connected to its neighbors in the four cardinal directions, within the grid.
+/// If `directed` is set, only the edges going right and down are stored, otherwise both directions are.
+/// Fails with `InvalidData` if the grid has no nodes, i.e. if `rows` or `cols` is 0.
+pub fn grid<N>(
+    rows: usize,
+    cols: usize,
+    directed: bool,
+    folder_name: &str,
+) -> Result<Graph<'static, N>, std::io::Error>
+where
+    N: ValidGraphType,
+{
+    // The neighbors of each node are listed in increasing order: up, left, right and down
+    let edges = (0..rows).flat_map(move |r| {
+        (0..cols).flat_map(move |c| {
+            let node = r * cols + c;
+            let up = (!directed && r > 0).then(|| node - cols);
+            let left = (!directed && c > 0).then(|| node - 1);
+            let right = (c + 1 < cols).then_some(node + 1);
+            let down = (r + 1 < rows).then_some(node + cols);
+
+            [up, left, right, down]
+                .into_iter()
+                .flatten()
+                .map(move |dst| {
+                    N::from_usize(node)
+                        .zip(N::from_usize(dst))
+                        .ok_or_else(|| std::io::ErrorKind::InvalidData.into())
+                })
+        })
+    });
+
+    Graph::from_sorted_edges(edges, rows * cols, folder_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    fn folder() -> String {
+        format!("/tmp/tmp_dst_{}", rand::random::<u32>())
+    }
+
+    #[test]
+    fn erdos_renyi_mean_degree() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let graph = erdos_renyi::<u32>(2000, 0.005, &mut rng, &folder()).unwrap();
+
+        assert_eq!(graph.n_nodes(), 2000);
+        assert!(!graph.has_self_loops());
+        assert!(graph.has_sorted_neighbors());
+
+        let mean_degree = graph.n_edges() as f64 / graph.n_nodes() as f64;
+        let expected = 1999.0 * 0.005;
+        assert!((mean_degree - expected).abs() < expected * 0.05);
+    }
+
+    #[test]
+    fn erdos_renyi_extremes() {
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let empty = erdos_renyi::<u32>(10, 0.0, &mut rng, &folder()).unwrap();
+        assert_eq!(empty.n_nodes(), 10);
+        assert_eq!(empty.n_edges(), 0);
+
+        let complete = erdos_renyi::<u32>(10, 1.0, &mut rng, &folder()).unwrap();
+        assert_eq!(complete.n_edges(), 90);
+        assert_eq!(complete.neighbors(3), &[0, 1, 2, 4, 5, 6, 7, 8, 9]);
+
+        assert!(erdos_renyi::<u32>(10, 1.5, &mut rng, &folder()).is_err());
+        assert!(erdos_renyi::<u32>(0, 0.5, &mut rng, &folder()).is_err());
+    }
+
+    #[test]
+    fn erdos_renyi_tiny_probability() {
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let graph = erdos_renyi::<u32>(50, 1e-20, &mut rng, &folder()).unwrap();
+        assert_eq!(graph.n_nodes(), 50);
+        assert_eq!(graph.n_edges(), 0);
+
+        // About 4 edges are expected out of the 999000 possible ones
+        let graph = erdos_renyi::<u32>(1000, 4e-6, &mut rng, &folder()).unwrap();
+        assert!(graph.n_edges() < 20);
+    }
+
+    #[test]
+    fn barabasi_albert_power_law() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let graph = barabasi_albert::<u32>(20_000, 3, &mut rng, &folder()).unwrap();
+
+        assert_eq!(graph.n_nodes(), 20_000);
+        // The initial clique has 6 edges, and every other node adds 3, in both directions
+        assert_eq!(graph.n_edges(), 2 * (6 + 3 * (20_000 - 4)));
+        assert!(!graph.has_self_loops());
+        assert!((0..graph.n_nodes()).all(|node| graph.degree(node) >= 3));
+
+        // The maximum likelihood estimate of the exponent of the degree distribution, which is 3 in theory
+        let k_min = 3.0;
+        let sum = (0..graph.n_nodes())
+            .map(|node| (graph.degree(node) as f64 / (k_min - 0.5)).ln())
+            .sum::<f64>();
+        let exponent = 1.0 + graph.n_nodes() as f64 / sum;
+        assert!((2.5..3.5).contains(&exponent), "exponent {}", exponent);
+
+        // Hubs are far above the average degree of 6
+        assert!(graph.max_degree().1 > 100);
+
+        assert!(barabasi_albert::<u32>(3, 3, &mut rng, &folder()).is_err());
+        assert!(barabasi_albert::<u32>(3, 0, &mut rng, &folder()).is_err());
+    }
+
+    #[test]
+    fn grid_neighbors() {
+        let directed = grid::<u32>(2, 3, true, &folder()).unwrap();
+        assert_eq!(
+            directed.iter().map(|x| x.to_vec()).collect::<Vec<_>>(),
+            vec![vec![1, 3], vec![2, 4], vec![5], vec![4], vec![5], vec![]]
+        );
+
+        let undirected = grid::<u32>(3, 3, false, &folder()).unwrap();
+        assert_eq!(undirected.n_nodes(), 9);
+        // 2 * (rows * (cols - 1) + (rows - 1) * cols)
+        assert_eq!(undirected.n_edges(), 24);
+        assert_eq!(undirected.neighbors(4), &[1, 3, 5, 7]);
+        assert_eq!(undirected.neighbors(0), &[1, 3]);
+        assert_eq!(undirected.neighbors(8), &[5, 7]);
+
+        assert!(grid::<u32>(0, 3, false, &folder()).is_err());
+        assert!(grid::<u32>(3, 0, true, &folder()).is_err());
+    }
+}
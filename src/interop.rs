@@ -0,0 +1,62 @@
+use petgraph::{graph::NodeIndex, Directed};
+
+use super::{util::ValidGraphType, Graph};
+
+/// Copies the graph into a [petgraph::Graph], with node `i` becoming `NodeIndex::new(i)`.
+/// Petgraph keeps every node and edge in its own heap-allocated structure, which takes several times the memory of the CSR,
+/// so very large graphs may exhaust the available memory. Both vectors are allocated upfront with the node and edge counts.
+impl<'a, N> From<&Graph<'a, N>> for petgraph::Graph<(), (), Directed>
+where
+    N: ValidGraphType,
+{
+    fn from(graph: &Graph<'a, N>) -> Self {
+        let mut result = petgraph::Graph::with_capacity(graph.n_nodes(), graph.n_edges());
+
+        for _ in 0..graph.n_nodes() {
+            result.add_node(());
+        }
+        for (src, edges) in graph.iter().enumerate() {
+            for dst in edges {
+                result.add_edge(NodeIndex::new(src), NodeIndex::new(dst.as_()), ());
+            }
+        }
+
+        result
+    }
+}
+
+/// Same as the conversion from `&Graph`, for when the CSR graph is no longer needed.
+impl<'a, N> From<Graph<'a, N>> for petgraph::Graph<(), (), Directed>
+where
+    N: ValidGraphType,
+{
+    fn from(graph: Graph<'a, N>) -> Self {
+        Self::from(&graph)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn into_petgraph() {
+        let graph = Graph::<u32>::from_adjacency_list(
+            vec![(0u32, 1u32), (0, 2), (1, 5), (1, 2), (4, 7)]
+                .into_iter()
+                .map(Ok),
+            &format!("/tmp/tmp_dst_{}", rand::random::<u32>()),
+        )
+        .unwrap();
+
+        let converted = petgraph::Graph::<(), (), Directed>::from(&graph);
+        assert_eq!(converted.node_count(), 8);
+        assert_eq!(converted.edge_count(), 5);
+        assert!(converted.contains_edge(NodeIndex::new(1), NodeIndex::new(5)));
+        assert!(!converted.contains_edge(NodeIndex::new(5), NodeIndex::new(1)));
+        assert_eq!(
+            petgraph::algo::connected_components(&petgraph::Graph::from(graph)),
+            4
+        );
+    }
+}
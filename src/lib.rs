@@ -1,24 +1,76 @@
-use std::io::{BufRead, BufReader, Read};
-
-use easy_mmap::{self, EasyMmap, EasyMmapBuilder};
-use rayon::prelude::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
-use reading::reader_to_iter;
+use std::collections::{HashSet, VecDeque};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use metadata::GraphMetadata;
+use rand::Rng;
+use rayon::prelude::{
+    IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator, ParallelIterator,
+    ParallelSlice, ParallelSliceMut,
+};
+use reading::{reader_to_iter, reader_to_iter_with_endianness};
+use storage::Storage;
 use util::ValidGraphType;
 
+#[cfg(feature = "petgraph")]
+mod interop;
 mod reading;
+mod storage;
+
+/// The errors reported by the crate
+pub mod error;
+
+/// The metadata stored alongside the graph files
+pub mod metadata;
+
+/// A configurable construction of graphs from adjacency lists
+pub mod builder;
+
+/// Graphs with a weight attached to each edge
+pub mod weighted;
 
 /// The generalized computational scheme for running algorithms
 pub mod compute;
 
+/// Ready-to-use graph algorithms built on top of the computational scheme
+pub mod algorithms;
+
+/// Node orderings that improve the locality of graphs
+pub mod ordering;
+
+/// Random sampling of nodes, edges and walks, e.g. for machine learning on graphs
+pub mod sampling;
+
+/// Graphs that store both their outgoing and incoming edges
+pub mod bidirectional;
+
+/// Synthetic graphs, e.g. for testing and benchmarking
+pub mod generators;
+
 /// A collection of convenient functions and traits to be used across the crate.
 pub mod util;
 
+/// The density below which [Graph::is_sparse_default] considers a graph sparse.
+pub const DEFAULT_SPARSITY_THRESHOLD: f64 = 1e-3;
+
+/// Describes what the caller knows about an adjacency list before it is converted into a graph.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ConstructionHint {
+    /// Every edge is checked to be sorted by source, and construction fails with `InvalidData` otherwise.
+    #[default]
+    CheckSorted,
+    /// The adjacency list is known to be sorted by source, so the check is skipped.
+    /// Passing an unsorted list with this hint results in an invalid graph.
+    TrustSorted,
+}
+
 /// This structure holds a graph in the Compressed Sparse Row format for compression of data size.
 /// This graph is represented via Memory Mapping, allowing the graph to be loaded into memory as required.
 /// This makes it possible to load any-size graphs, even those that *do not* fit into memory!
+/// Small graphs can also be kept purely in memory, see [from_csr_vecs](Self::from_csr_vecs).
 pub struct Graph<'a, N> {
-    nodes: EasyMmap<'a, usize>,
-    edges: EasyMmap<'a, N>,
+    nodes: Storage<'a, usize>,
+    edges: Storage<'a, N>,
 }
 
 impl<'a, N> Graph<'a, N>
@@ -37,28 +89,195 @@ where
         T: Read + Sized,
     {
         let reader = BufReader::new(stream);
-        let stream = reader.lines().map(|line| {
-            let line = line?;
-            let mut parts = line.split_whitespace();
-
-            let src = parts
-                .next()
-                .ok_or(std::io::ErrorKind::InvalidData)?
-                .parse::<N>()
-                .or(Err(std::io::ErrorKind::InvalidData))?;
-
-            let dst = parts
-                .next()
-                .ok_or(std::io::ErrorKind::InvalidData)?
-                .parse::<N>()
-                .or(Err(std::io::ErrorKind::InvalidData))?;
-
-            std::io::Result::Ok((src, dst))
-        });
+        let stream = reader
+            .lines()
+            .map(|line| reading::parse_txt_edge::<N>(&line?));
+
+        Graph::from_adjacency_list(stream, folder_name)
+    }
+
+    /// Same as [from_txt_adjacency_list](Self::from_txt_adjacency_list), except the file at `path` is parsed by `num_threads` threads.
+    /// The file is memory mapped and split into one chunk per thread at line boundaries, each chunk is parsed and sorted independently,
+    /// and the sorted chunks are merged while writing the graph. The result is the same as reading the file with
+    /// [from_txt_adjacency_list](Self::from_txt_adjacency_list), except the file does not need to be sorted by source.
+    pub fn from_edge_list_file_parallel(
+        path: &str,
+        num_threads: usize,
+        folder_name: &str,
+    ) -> Result<Graph<'a, N>, std::io::Error>
+    where
+        N: Send + Sync,
+    {
+        let file = std::fs::File::open(path)?;
+        let input = Storage::<u8>::map_file_readonly(file);
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build()
+            .map_err(std::io::Error::other)?;
+
+        let runs = pool.install(|| {
+            reading::split_at_lines(input.get_data_as_slice(), num_threads)
+                .into_par_iter()
+                .map(|chunk| {
+                    let mut edges = std::str::from_utf8(chunk)
+                        .or(Err(std::io::ErrorKind::InvalidData))?
+                        .lines()
+                        .map(reading::parse_txt_edge::<N>)
+                        .collect::<std::io::Result<Vec<_>>>()?;
+                    edges.sort_unstable_by_key(|(src, dst)| (src.as_(), dst.as_()));
+
+                    Ok(edges)
+                })
+                .collect::<std::io::Result<Vec<_>>>()
+        })?;
+
+        Graph::from_adjacency_list_with_hint(
+            reading::merge_sorted_runs(runs).map(Ok),
+            folder_name,
+            ConstructionHint::TrustSorted,
+        )
+    }
+
+    /// Checks whether the text adjacency list in `stream` is sorted by source, as required by
+    /// [from_txt_adjacency_list](Self::from_txt_adjacency_list), without constructing the graph.
+    /// The first edge out of order is returned as [GraphError::UnsortedEdge](error::GraphError::UnsortedEdge), with its line.
+    /// Fails as well if a line cannot be read or parsed.
+    pub fn check_sorted<T>(stream: T) -> Result<(), error::GraphError>
+    where
+        T: Read,
+    {
+        reading::check_sorted::<N, T>(stream)
+    }
+
+    /// Convenience method for reading an input stream in CSV format.
+    /// Each line should contain two numbers, separated by `sep`. Any additional fields are ignored, and so are empty lines.
+    /// If `skip_header` is set, the first line of the stream is discarded.
+    /// The graph will be converted to the underlying CSR representation, and stored in `folder_name`.
+    pub fn from_csv<T>(
+        stream: T,
+        sep: char,
+        skip_header: bool,
+        folder_name: &str,
+    ) -> Result<Graph<'a, N>, std::io::Error>
+    where
+        T: Read + Sized,
+    {
+        let reader = BufReader::new(stream);
+        let stream = reader
+            .lines()
+            .skip(if skip_header { 1 } else { 0 })
+            .filter(|line| !matches!(line, Ok(line) if line.trim().is_empty()))
+            .map(|line| {
+                let line = line?;
+                let mut parts = line.split(sep).map(|part| part.trim());
+
+                let src = parts
+                    .next()
+                    .ok_or(std::io::ErrorKind::InvalidData)?
+                    .parse::<N>()
+                    .or(Err(std::io::ErrorKind::InvalidData))?;
+
+                let dst = parts
+                    .next()
+                    .ok_or(std::io::ErrorKind::InvalidData)?
+                    .parse::<N>()
+                    .or(Err(std::io::ErrorKind::InvalidData))?;
+
+                std::io::Result::Ok((src, dst))
+            });
 
         Graph::from_adjacency_list(stream, folder_name)
     }
 
+    /// Reads a sparse adjacency matrix in the MatrixMarket (`.mtx`) coordinate format.
+    /// Both `general` and `symmetric` matrices are supported, and their entries do not need to be sorted.
+    /// Since entries are sorted in memory before construction, the whole edge list must fit in memory.
+    /// Weighted matrices are not supported yet: their value field is ignored and only the topology is kept.
+    /// The graph has as many nodes as the largest dimension of the matrix, even if the last rows and columns are empty.
+    pub fn from_matrix_market<T>(
+        stream: T,
+        folder_name: &str,
+    ) -> Result<Graph<'a, N>, std::io::Error>
+    where
+        T: Read + Sized,
+    {
+        let (n_nodes, edges) = reading::matrix_market_entries::<N, T>(stream)?;
+
+        Graph::from_sorted_edges(edges.into_iter().map(Ok), n_nodes, folder_name)
+    }
+
+    /// Reads a graph in the Pajek (`.net`) format, made of a `*Vertices N` section followed by `*Arcs` or `*Edges` sections
+    /// of 1-indexed node pairs. The lines of `*Edges` sections are undirected, so both directions of each edge are added.
+    /// Vertex labels are skipped, and weights are dropped without any warning: only the topology of the graph is kept.
+    /// The `*Network` header and unsupported sections such as `*Partition` or `*Vector` are skipped as well.
+    /// The graph always has the `N` nodes declared by the vertices section, even if the last ones have no edges.
+    pub fn from_pajek<T>(stream: T, folder_name: &str) -> Result<Graph<'a, N>, std::io::Error>
+    where
+        T: Read + Sized,
+    {
+        let (n_nodes, edges) = reading::pajek_entries::<N, T>(stream)?;
+
+        Graph::from_sorted_edges(edges.into_iter().map(Ok), n_nodes, folder_name)
+    }
+
+    /// Reads a graph stored in the binary adjacency format of the Ligra framework and the GAP benchmarks, from the file `adj_file`.
+    /// The file starts with the number of nodes and edges, followed by the node offsets and the edge destinations, all as
+    /// native-endian 64-bit values. As this is already a CSR layout, it is copied into the graph files without any parsing.
+    pub fn from_ligra_binary(
+        adj_file: &str,
+        folder_name: &str,
+    ) -> Result<Graph<'a, N>, std::io::Error> {
+        reading::ligra_binary_to_csr::<N>(adj_file, folder_name)?;
+
+        Self::load_graph(folder_name)
+    }
+
+    /// Reads a graph in the binary format of the DIMACS shortest path challenge.
+    /// Each record holds four native-endian `u32` values: a sentinel (`b'a'`), the source, the destination, and the weight.
+    /// Node identifiers are 1-indexed and converted to 0-indexed nodes, and weights are ignored.
+    /// Since arcs are sorted in memory before construction, the whole edge list must fit in memory.
+    /// The format does not store the number of nodes, so the graph ends at the highest node with an arc,
+    /// see [from_dimacs_binary_with_n_nodes](Self::from_dimacs_binary_with_n_nodes) to keep isolated nodes after it.
+    pub fn from_dimacs_binary<T>(
+        stream: T,
+        folder_name: &str,
+    ) -> Result<Graph<'a, N>, std::io::Error>
+    where
+        T: Read + Sized,
+    {
+        let edges = reading::dimacs_binary_entries::<N, T>(stream, usize::MAX)?;
+
+        Graph::from_adjacency_list(edges.into_iter().map(Ok), folder_name)
+    }
+
+    /// Same as [from_dimacs_binary](Self::from_dimacs_binary), for a graph known to have `n_nodes` nodes.
+    /// The graph always has `n_nodes` nodes, even if the last ones have no arcs, and arcs beyond them are rejected with `InvalidData`.
+    pub fn from_dimacs_binary_with_n_nodes<T>(
+        stream: T,
+        n_nodes: usize,
+        folder_name: &str,
+    ) -> Result<Graph<'a, N>, std::io::Error>
+    where
+        T: Read + Sized,
+    {
+        let edges = reading::dimacs_binary_entries::<N, T>(stream, n_nodes)?;
+
+        Graph::from_sorted_edges(edges.into_iter().map(Ok), n_nodes, folder_name)
+    }
+
+    /// Same as [from_dimacs_binary](Self::from_dimacs_binary), except this time it reads the text variant,
+    /// made of a `p sp N M` problem line followed by `a U V W` arc lines.
+    /// The graph always has the `N` nodes declared by the problem line, even if the last ones have no arcs.
+    pub fn from_dimacs_text<T>(stream: T, folder_name: &str) -> Result<Graph<'a, N>, std::io::Error>
+    where
+        T: Read + Sized,
+    {
+        let (n_nodes, edges) = reading::dimacs_text_entries::<N, T>(stream)?;
+
+        Graph::from_sorted_edges(edges.into_iter().map(Ok), n_nodes, folder_name)
+    }
+
     /// Same as [from_txt_adjacency](Self::from_txt_adjacency_list), except this time it assumes the edge list to be in binary representation.
     pub fn from_binary_adjancency<T>(
         stream: T,
@@ -68,14 +287,64 @@ where
         T: Read + Sized,
     {
         Graph::from_adjacency_list(
-            reader_to_iter::<N, T>(stream).map(|x| std::io::Result::Ok(x)),
+            reader_to_iter::<N, T>(stream).map(Ok),
+            destination_folder_name,
+        )
+    }
+
+    /// Same as [from_binary_adjancency](Self::from_binary_adjancency), except the numbers in `stream` are in the given byte order,
+    /// so that files written on a machine with a different byte order can be read.
+    pub fn from_binary_adjancency_with_endianness<T>(
+        stream: T,
+        destination_folder_name: &str,
+        endianness: util::Endianness,
+    ) -> Result<Graph<'a, N>, std::io::Error>
+    where
+        T: Read + Sized,
+    {
+        Graph::from_adjacency_list(
+            reader_to_iter_with_endianness::<N, T>(stream, endianness).map(Ok),
             destination_folder_name,
         )
     }
 
+    /// Constructs a graph from a square adjacency matrix, with an edge `i -> j` for every `matrix[i][j]` that is `true`.
+    /// The graph has one node per row, even if the last ones have no edges, and is stored in `folder_name`.
+    /// Fails with `InvalidData` if any row does not have as many columns as there are rows.
+    /// This is intended for small graphs, as the matrix takes quadratic space.
+    pub fn from_adjacency_matrix<M>(
+        matrix: M,
+        folder_name: &str,
+    ) -> Result<Graph<'a, N>, std::io::Error>
+    where
+        M: AsRef<[Vec<bool>]>,
+    {
+        let matrix = matrix.as_ref();
+        let n_nodes = matrix.len();
+        if matrix.iter().any(|row| row.len() != n_nodes) {
+            Err(std::io::ErrorKind::InvalidData)?;
+        }
+
+        // Going through the rows in order yields the edges sorted by source, and then by destination
+        let stream = matrix.iter().enumerate().flat_map(|(src, row)| {
+            row.iter()
+                .enumerate()
+                .filter(|(_, edge)| **edge)
+                .map(move |(dst, _)| {
+                    N::from_usize(src)
+                        .zip(N::from_usize(dst))
+                        .ok_or_else(|| std::io::ErrorKind::InvalidData.into())
+                })
+        });
+
+        Graph::from_sorted_edges(stream, n_nodes, folder_name)
+    }
+
     /// Given a SORTED (by source) adjancency list file `source_file_name`, transforms this file
     /// into the underlying binary representation in CSR and returns a version of the Graph in this format.
-    /// The graph will be stored in `folder_name`.
+    /// The graph will be stored in `folder_name`, with the edges of each node sorted by destination.
+    /// If `folder_name` already exists, it is reused and its graph files are replaced. To fail or to start from an empty folder instead,
+    /// see [OverwritePolicy](builder::OverwritePolicy) in the [GraphBuilder](builder::GraphBuilder).
     pub fn from_adjacency_list<T>(
         stream: T,
         folder_name: &str,
@@ -88,40 +357,329 @@ where
         Self::load_graph(folder_name)
     }
 
+    /// Same as [from_adjacency_list](Self::from_adjacency_list), except `on_progress` is called with the number of edges
+    /// processed so far every `interval` edges (10 000 000 if `None`), and once more when all edges are processed,
+    /// unless the total is a multiple of `interval` and was therefore just reported.
+    /// This allows reporting the progress of long constructions, e.g. with a progress bar.
+    pub fn from_adjacency_list_with_progress<T, F>(
+        stream: T,
+        folder_name: &str,
+        interval: Option<u64>,
+        on_progress: Option<F>,
+    ) -> Result<Graph<'a, N>, std::io::Error>
+    where
+        T: Iterator<Item = std::io::Result<(N, N)>> + Sized,
+        F: FnMut(u64),
+    {
+        match on_progress {
+            Some(on_progress) => {
+                reading::from_adjacency_list_with_progress::<N, T, F>(
+                    stream,
+                    folder_name,
+                    interval.unwrap_or(reading::DEFAULT_PROGRESS_INTERVAL),
+                    on_progress,
+                )?;
+            }
+            None => {
+                reading::from_adjacency_list::<N, T>(stream, folder_name)?;
+            }
+        }
+
+        Self::load_graph(folder_name)
+    }
+
+    /// Same as [from_adjacency_list](Self::from_adjacency_list), except `hint` can be used to skip the sort check
+    /// when the adjacency list is already known to be sorted.
+    pub fn from_adjacency_list_with_hint<T>(
+        stream: T,
+        folder_name: &str,
+        hint: ConstructionHint,
+    ) -> Result<Graph<'a, N>, std::io::Error>
+    where
+        T: Iterator<Item = std::io::Result<(N, N)>> + Sized,
+    {
+        reading::from_adjacency_list_with_hint::<N, T>(stream, folder_name, hint)?;
+
+        Self::load_graph(folder_name)
+    }
+
+    /// Writes a graph whose CSR arrays were already built in memory to `folder_name`, without going through an edge list.
+    /// `nodes[i]..nodes[i + 1]` must index the edges of node `i` in `edges`, so `nodes` must start at 0, be non-decreasing,
+    /// and end at `edges.len()`. All edges must also point to existing nodes. Otherwise, `InvalidData` is returned.
+    pub fn from_csr_arrays(
+        nodes: &[usize],
+        edges: &[N],
+        folder_name: &str,
+    ) -> Result<Graph<'a, N>, std::io::Error> {
+        reading::write_csr_arrays(nodes, edges, folder_name)?;
+
+        Self::load_graph(folder_name)
+    }
+
+    /// Creates a graph that is purely kept in memory from its CSR arrays, without requiring any disk backing.
+    /// The arrays must follow the same rules as in [from_csr_arrays](Self::from_csr_arrays), otherwise `InvalidData` is returned.
+    pub fn from_csr_vecs(nodes: Vec<usize>, edges: Vec<N>) -> Result<Graph<'a, N>, std::io::Error> {
+        reading::validate_csr_arrays(&nodes, &edges)?;
+
+        Ok(Graph {
+            nodes: Storage::Memory(nodes),
+            edges: Storage::Memory(edges),
+        })
+    }
+
+    /// Constructs a graph with at least `n_nodes` nodes from a stream of edges sorted by source.
+    /// Fails with `InvalidData` if `n_nodes` is 0.
+    pub(crate) fn from_sorted_edges<T>(
+        stream: T,
+        n_nodes: usize,
+        folder_name: &str,
+    ) -> Result<Graph<'a, N>, std::io::Error>
+    where
+        T: Iterator<Item = std::io::Result<(N, N)>> + Sized,
+    {
+        reading::from_sorted_edges::<N, T>(stream, n_nodes, folder_name)?;
+
+        Self::load_graph(folder_name)
+    }
+
     /// Loads a graph from the underlying representation and returns it as a `Graph` struct.
+    /// The files are only opened for reading, see [load_graph_readonly](Self::load_graph_readonly).
+    /// If the folder contains a metadata sidecar, the graph is validated against it, and a
+    /// [GraphError::MetadataMismatch](error::GraphError::MetadataMismatch) is returned as the inner error on mismatch.
     pub fn load_graph(graph_folder: &str) -> Result<Graph<'a, N>, std::io::Error> {
+        Self::load_graph_readonly(graph_folder)
+    }
+
+    /// Same as [load_graph](Self::load_graph), making it explicit that the graph files are only opened for reading.
+    /// This works on files owned by other users or on read-only filesystems, and fails with `NotFound` if a file is missing.
+    pub fn load_graph_readonly(graph_folder: &str) -> Result<Graph<'a, N>, std::io::Error> {
+        let nodes_file = reading::open_file_readonly(graph_folder, reading::VERTEX_NAME)?;
+        let edges_file = reading::open_file_readonly(graph_folder, reading::EDGE_NAME)?;
+
+        Self::validate_and_map(Some(graph_folder), nodes_file, edges_file, true)
+    }
+
+    /// Same as [load_graph](Self::load_graph), except the graph files are opened for reading and writing,
+    /// and created empty if they do not exist, as every load did before read-only loading became the default.
+    pub fn load_graph_readwrite(graph_folder: &str) -> Result<Graph<'a, N>, std::io::Error> {
         let nodes_file = reading::get_vertex_file(graph_folder)?;
         let edges_file = reading::get_edge_file(graph_folder)?;
 
-        let nodes = EasyMmapBuilder::<usize>::new()
-            .capacity(
-                nodes_file
-                    .metadata()
-                    .expect("Failed to read metadata of vertex file")
-                    .len() as usize
-                    / std::mem::size_of::<usize>(),
-            )
-            .file(nodes_file)
-            .readable()
-            .build();
-
-        let edges = EasyMmapBuilder::<N>::new()
-            .capacity(
-                edges_file
-                    .metadata()
-                    .expect("Failed to read metadata of edge file")
-                    .len() as usize
-                    / std::mem::size_of::<N>(),
-            )
-            .file(edges_file)
-            .readable()
-            .build();
+        Self::validate_and_map(Some(graph_folder), nodes_file, edges_file, false)
+    }
+
+    /// Returns a [GraphBuilder](builder::GraphBuilder) that names the vertex and edge files `vertex_name` and `edge_name`,
+    /// so that several graphs, e.g. a graph and its reverse, can be stored in the same folder.
+    /// Such graphs are loaded back with [load_graph_with_file_names](Self::load_graph_with_file_names).
+    pub fn with_custom_file_names(vertex_name: &str, edge_name: &str) -> builder::GraphBuilder<N> {
+        builder::GraphBuilder::new()
+            .vertex_file(vertex_name)
+            .edge_file(edge_name)
+    }
+
+    /// Same as [load_graph](Self::load_graph), except the vertex and edge files of `graph_folder` are named `vertex_name` and `edge_name`.
+    /// The metadata sidecar only describes the default files, so it is not checked unless those are the names given.
+    pub fn load_graph_with_file_names(
+        graph_folder: &str,
+        vertex_name: &str,
+        edge_name: &str,
+    ) -> Result<Graph<'a, N>, std::io::Error> {
+        let nodes_file = reading::open_file_readonly(graph_folder, vertex_name)?;
+        let edges_file = reading::open_file_readonly(graph_folder, edge_name)?;
+
+        let is_default = vertex_name == reading::VERTEX_NAME && edge_name == reading::EDGE_NAME;
+        Self::validate_and_map(
+            is_default.then_some(graph_folder),
+            nodes_file,
+            edges_file,
+            true,
+        )
+    }
+
+    /// Validates the opened graph files against the metadata sidecar of `metadata_folder`, if any, and maps them.
+    fn validate_and_map(
+        metadata_folder: Option<&str>,
+        nodes_file: std::fs::File,
+        edges_file: std::fs::File,
+        read_only: bool,
+    ) -> Result<Graph<'a, N>, std::io::Error> {
+        let metadata = match metadata_folder {
+            Some(folder) => GraphMetadata::load(folder)?,
+            None => None,
+        };
+        if let Some(metadata) = metadata {
+            let n_offsets = nodes_file.metadata()?.len() as usize / std::mem::size_of::<usize>();
+            let n_edges = edges_file.metadata()?.len() as usize / std::mem::size_of::<N>();
+
+            metadata.validate::<N>(n_offsets.saturating_sub(1), n_edges)?;
+        }
+
+        let graph = if read_only {
+            Graph {
+                nodes: Storage::map_file_readonly(nodes_file),
+                edges: Storage::map_file_readonly(edges_file),
+            }
+        } else {
+            Self::load_from_files(nodes_file, edges_file)?
+        };
+
+        #[cfg(debug_assertions)]
+        graph.verify_integrity()?;
+
+        Ok(graph)
+    }
+
+    /// Checks that the CSR arrays of the graph are consistent: `nodes` must start at 0, be non-decreasing
+    /// and end at the number of edges, and every edge must point to an existing node.
+    /// Otherwise, a [GraphError::CorruptedCsr](error::GraphError::CorruptedCsr) describing the broken invariant is returned.
+    /// This is a single pass over both arrays, and runs on every [load_graph](Self::load_graph) in debug builds.
+    pub fn verify_integrity(&self) -> Result<(), error::GraphError> {
+        use error::GraphError::CorruptedCsr;
+
+        let (nodes, edges) = self.as_csr_slices();
+
+        let last = *nodes
+            .last()
+            .ok_or(CorruptedCsr("the vertex file is empty"))?;
+        if nodes[0] != 0 {
+            return Err(CorruptedCsr("the first offset is not 0"));
+        }
+        if nodes.windows(2).any(|w| w[0] > w[1]) {
+            return Err(CorruptedCsr("the offsets are not sorted"));
+        }
+        if last != edges.len() {
+            return Err(CorruptedCsr(
+                "the last offset does not match the number of edges",
+            ));
+        }
+        if edges.iter().any(|edge| edge.as_() >= nodes.len() - 1) {
+            return Err(CorruptedCsr("an edge points to a node that does not exist"));
+        }
+
+        Ok(())
+    }
+
+    /// Panics with a message naming the offending node or edge if the CSR arrays break one of the invariants checked by
+    /// [verify_integrity](Self::verify_integrity). Meant for tests that build graphs by hand, so it does nothing in release builds.
+    pub fn assert_invariants(&self) {
+        #[cfg(debug_assertions)]
+        {
+            let (nodes, edges) = self.as_csr_slices();
+
+            assert!(!nodes.is_empty(), "the vertex array is empty");
+            assert_eq!(nodes[0], 0, "the first offset is not 0");
+            if let Some(node) = nodes.windows(2).position(|w| w[0] > w[1]) {
+                panic!(
+                    "the offsets decrease at node {}: {} > {}",
+                    node,
+                    nodes[node],
+                    nodes[node + 1]
+                );
+            }
+            assert_eq!(
+                nodes[nodes.len() - 1],
+                edges.len(),
+                "the last offset does not match the number of edges"
+            );
+            if let Some(edge) = edges.iter().position(|e| e.as_() >= nodes.len() - 1) {
+                panic!(
+                    "edge {} points to node {}, but there are only {} nodes",
+                    edge,
+                    edges[edge],
+                    nodes.len() - 1
+                );
+            }
+        }
+    }
+
+    /// Memory maps the already opened vertex and edge files into a `Graph` struct.
+    pub(crate) fn load_from_files(
+        nodes_file: std::fs::File,
+        edges_file: std::fs::File,
+    ) -> Result<Graph<'a, N>, std::io::Error> {
+        Ok(Graph {
+            nodes: Storage::map_file(nodes_file),
+            edges: Storage::map_file(edges_file),
+        })
+    }
+
+    /// Loads the graph stored in `graph_folder` together with the attribute of each of its nodes, as written by [save_attributes](Self::save_attributes).
+    /// Returns `InvalidData` if the attribute file does not hold exactly one attribute of type `A` per node.
+    pub fn load_with_attributes<A>(
+        graph_folder: &str,
+    ) -> Result<(Graph<'a, N>, Vec<A>), std::io::Error>
+    where
+        A: util::GraphData,
+    {
+        let graph = Self::load_graph(graph_folder)?;
+
+        let file = reading::open_file_readonly(graph_folder, util::ATTRIBUTE_NAME)?;
+        if file.metadata()?.len() as usize != graph.n_nodes() * std::mem::size_of::<A>() {
+            Err(std::io::ErrorKind::InvalidData)?;
+        }
+        let attributes = Storage::<A>::map_file_readonly(file)
+            .get_data_as_slice()
+            .to_vec();
+
+        Ok((graph, attributes))
+    }
+
+    /// Stores one attribute per node, such as a label or a feature, in [ATTRIBUTE_NAME](util::ATTRIBUTE_NAME) inside `folder_name`,
+    /// which should be the folder of this graph. Returns `InvalidData` if there is not exactly one attribute per node.
+    pub fn save_attributes<A>(&self, attributes: &[A], folder_name: &str) -> std::io::Result<()>
+    where
+        A: util::GraphData,
+    {
+        if attributes.len() != self.n_nodes() {
+            Err(std::io::ErrorKind::InvalidData)?;
+        }
+
+        let mut writer = std::io::BufWriter::new(std::fs::File::create(format!(
+            "{}/{}",
+            folder_name,
+            util::ATTRIBUTE_NAME
+        ))?);
+        for attribute in attributes {
+            attribute.write_self(&mut writer)?;
+        }
+        writer.flush()
+    }
 
-        Ok(Graph { nodes, edges })
+    /// Copies the graph into `folder_name`, leaving this graph untouched, and returns the copy.
+    /// The graph does not keep track of its own folder, so its arrays are written from memory, which works for in-memory graphs too.
+    /// A new metadata sidecar is written for the copy, but other files of the original folder are not copied, e.g. the
+    /// [node attributes](Self::save_attributes), the renaming table of a reordering, or files with custom names.
+    /// The files are first written to a temporary folder next to `folder_name`, which is then renamed, so a failed copy never leaves
+    /// a partial graph behind. Fails if `folder_name` already exists and is not empty.
+    pub fn clone_to(&self, folder_name: &str) -> Result<Graph<'a, N>, std::io::Error> {
+        let temporary_folder = format!("{}.tmp_{}", folder_name, rand::random::<u32>());
+        let sorted = self
+            .iter()
+            .all(|edges| edges.windows(2).all(|w| w[0].as_() <= w[1].as_()));
+
+        let result = reading::write_csr_arrays(
+            self.nodes.get_data_as_slice(),
+            self.edges.get_data_as_slice(),
+            &temporary_folder,
+        )
+        .and_then(|_| {
+            GraphMetadata::new::<N>(self.n_nodes(), self.n_edges())
+                .with_edges_sorted_per_node(sorted)
+                .save(&temporary_folder)
+        })
+        .and_then(|_| std::fs::rename(&temporary_folder, folder_name));
+
+        if let Err(e) = result {
+            let _ = std::fs::remove_dir_all(&temporary_folder);
+            return Err(e);
+        }
+
+        Self::load_graph(folder_name)
     }
 
     /// Returns an iterator over the edge list of each node.
-    pub fn iter(&'a self) -> impl Iterator<Item = &[N]> + 'a {
+    /// Use [GraphIterator::skip_to] to start from another node than 0.
+    pub fn iter(&'a self) -> GraphIterator<'a, N> {
         GraphIterator {
             nodes: self.nodes.get_data_as_slice(),
             edges: self.edges.get_data_as_slice(),
@@ -129,27 +687,60 @@ where
         }
     }
 
-    pub fn par_iter(&'a self) -> impl ParallelIterator<Item = (usize, &[N])> + 'a
+    pub fn par_iter(&'a self) -> impl ParallelIterator<Item = (usize, &'a [N])> + 'a
     where
         N: Send + Sync,
     {
-        GraphIterator {
+        ParGraphIterator(GraphIterator {
             nodes: self.nodes.get_data_as_slice(),
             edges: self.edges.get_data_as_slice(),
             current_node: 0,
-        }
+        })
     }
 
     #[inline]
     #[allow(dead_code)]
     fn iterate_nodes(&'a self) -> impl Iterator<Item = usize> + 'a {
-        self.nodes.iter().map(|x| *x)
+        self.nodes.iter().copied()
     }
 
     #[inline]
     #[allow(dead_code)]
     fn iterate_edges(&'a self) -> impl Iterator<Item = N> + 'a {
-        self.edges.iter().map(|x| *x)
+        self.edges.iter().copied()
+    }
+
+    /// Returns an iterator over every edge of the graph as a `(source, destination)` pair, in the order they are stored.
+    pub fn edge_iter(&self) -> impl Iterator<Item = (usize, N)> + '_ {
+        (0..self.n_nodes())
+            .flat_map(move |src| self.neighbors(src).iter().map(move |dst| (src, *dst)))
+    }
+
+    /// Same as [edge_iter](Self::edge_iter), except the edges are processed in parallel, with each node's edges handled by one task.
+    pub fn par_edge_iter(&self) -> impl ParallelIterator<Item = (usize, N)> + '_
+    where
+        N: Send + Sync,
+    {
+        (0..self.n_nodes())
+            .into_par_iter()
+            .flat_map_iter(move |src| self.neighbors(src).iter().map(move |dst| (src, *dst)))
+    }
+
+    /// Returns read-only views of the CSR arrays of the graph, without copying them.
+    /// The edges of node `i` are `edges[nodes[i]..nodes[i + 1]]`, so `nodes` has one more element than there are nodes.
+    pub fn as_csr_slices(&self) -> (&[usize], &[N]) {
+        (
+            self.nodes.get_data_as_slice(),
+            self.edges.get_data_as_slice(),
+        )
+    }
+
+    /// Same as [as_csr_slices](Self::as_csr_slices), except the arrays are copied into vectors,
+    /// e.g. to hand them over to other libraries. They can be turned back into a graph with [from_csr_vecs](Self::from_csr_vecs).
+    pub fn to_csr_arrays(&self) -> (Vec<usize>, Vec<N>) {
+        let (nodes, edges) = self.as_csr_slices();
+
+        (nodes.to_vec(), edges.to_vec())
     }
 
     /// Returns the number of nodes existing in the graph
@@ -161,277 +752,2362 @@ where
     pub fn n_edges(&self) -> usize {
         self.edges.len()
     }
-}
 
-/// Iterates over a [Graph] struct and yields the outgoing edge lists of type `&[N]` for each node.
-pub struct GraphIterator<'a, N> {
-    nodes: &'a [usize],
-    edges: &'a [N],
-    current_node: usize,
-}
+    /// Returns how many bytes the vertex and edge arrays take, in this order.
+    /// For mapped graphs this is the size of the mapped regions, which can be larger than what is actually resident in memory.
+    pub fn memory_usage(&self) -> (usize, usize) {
+        (
+            self.nodes.len() * std::mem::size_of::<usize>(),
+            self.edges.len() * std::mem::size_of::<N>(),
+        )
+    }
 
-impl<'a, N> Iterator for GraphIterator<'a, N>
-where
-    N: ValidGraphType,
-{
-    type Item = &'a [N];
+    /// Returns the total bytes taken by the vertex and edge arrays, see [memory_usage](Self::memory_usage).
+    pub fn total_memory_bytes(&self) -> usize {
+        let (nodes_bytes, edges_bytes) = self.memory_usage();
+        nodes_bytes + edges_bytes
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.current_node >= self.nodes.len() - 1 {
-            return None;
-        };
+    /// Returns the outgoing edge list of `node`.
+    #[inline]
+    pub fn neighbors(&self, node: usize) -> &[N] {
+        let nodes = self.nodes.get_data_as_slice();
+        &self.edges.get_data_as_slice()[nodes[node]..nodes[node + 1]]
+    }
 
-        let start = self.nodes[self.current_node];
-        let end = self.nodes[self.current_node + 1];
+    /// Returns the out-degree of `node`.
+    #[inline]
+    pub fn degree(&self, node: usize) -> usize {
+        self.nodes[node + 1] - self.nodes[node]
+    }
 
-        self.current_node += 1;
+    /// Returns how many nodes have an id `x` such that `low <= x < high`, or 0 if `high <= low`.
+    pub fn node_count_in_range(&self, low: N, high: N) -> usize {
+        let high = high.as_().min(self.n_nodes());
+        high.saturating_sub(low.as_())
+    }
 
-        Some(&self.edges[start..end])
+    /// Returns the sum of the out-degrees of the nodes with an id `x` such that `low <= x < high`, or 0 if `high <= low`.
+    /// Since the edges of consecutive nodes are contiguous, this only reads two offsets.
+    pub fn edge_count_in_node_range(&self, low: N, high: N) -> usize {
+        let high = high.as_().min(self.n_nodes());
+        let low = low.as_().min(high);
+        self.nodes[high] - self.nodes[low]
     }
-}
 
-impl<'a, N> ParallelIterator for GraphIterator<'a, N>
-where
-    N: ValidGraphType + Send + Sync,
-{
-    type Item = (usize, &'a [N]);
+    /// Returns whether the edge list of every node is sorted in increasing order.
+    /// Graphs constructed from adjacency lists always satisfy this, as the edges of each node are sorted during construction.
+    pub fn has_sorted_neighbors(&self) -> bool
+    where
+        N: Send + Sync,
+    {
+        self.par_iter()
+            .all(|(_, edges)| edges.windows(2).all(|w| w[0].as_() <= w[1].as_()))
+    }
 
-    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    /// Returns whether any node has an edge to itself, stopping at the first one found.
+    pub fn has_self_loops(&self) -> bool
     where
-        C: rayon::iter::plumbing::UnindexedConsumer<Self::Item>,
+        N: Send + Sync,
     {
-        self.nodes[..self.nodes.len() - 1]
-            .par_iter()
-            .enumerate()
-            .zip(self.nodes[1..].par_iter())
-            .map(|((idx, start), end)| {
+        self.par_iter()
+            .any(|(node, edges)| edges.iter().any(|e| e.as_() == node))
+    }
+
+    /// Returns the nodes that have at least one edge to themselves, in increasing order.
+    pub fn self_loop_nodes(&self) -> Vec<usize>
+    where
+        N: Send + Sync,
+    {
+        self.par_iter()
+            .filter(|(node, edges)| edges.iter().any(|e| e.as_() == *node))
+            .map(|(node, _)| node)
+            .collect()
+    }
+
+    /// Returns a parallel iterator over the out-degree of each node.
+    fn par_degrees(&self) -> impl IndexedParallelIterator<Item = usize> + '_ {
+        self.nodes
+            .get_data_as_slice()
+            .par_windows(2)
+            .map(|w| w[1] - w[0])
+    }
+
+    /// Returns the out-degree histogram of the graph, where index `k` holds the number of nodes with out-degree `k`.
+    pub fn degree_distribution(&self) -> Vec<usize> {
+        self.par_degrees()
+            .fold(Vec::new, |mut histogram, degree| {
+                if histogram.len() <= degree {
+                    histogram.resize(degree + 1, 0);
+                }
+                histogram[degree] += 1;
+                histogram
+            })
+            .reduce(Vec::new, |mut a, mut b| {
+                if a.len() < b.len() {
+                    std::mem::swap(&mut a, &mut b);
+                }
+                a.iter_mut().zip(b).for_each(|(x, y)| *x += y);
+                a
+            })
+    }
+
+    /// Returns the out-degree of every node, sorted in non-increasing order.
+    pub fn degree_sequence(&self) -> Vec<usize> {
+        let mut degrees = self.par_degrees().collect::<Vec<_>>();
+        degrees.par_sort_unstable_by(|a, b| b.cmp(a));
+        degrees
+    }
+
+    /// Returns the node at position `rank` when the nodes are ordered by decreasing out-degree, so rank 0 is the node with the highest degree.
+    /// Ties are broken in favour of the lowest node id, as in [sorted_by_degree](Self::sorted_by_degree).
+    /// Only a partial sort is performed, with `select_nth_unstable`. Panics if `rank` is not smaller than the number of nodes.
+    pub fn node_at_rank(&self, rank: usize) -> usize {
+        let mut nodes = (0..self.n_nodes()).collect::<Vec<_>>();
+        let (_, node, _) = nodes.select_nth_unstable_by_key(rank, |node| {
+            (std::cmp::Reverse(self.degree(*node)), *node)
+        });
+        *node
+    }
+
+    /// Returns the nodes as `(node_id, degree)` pairs in decreasing order of out-degree, with ties broken in favour of the lowest node id.
+    pub fn iter_by_degree_desc(&self) -> impl Iterator<Item = (usize, usize)> {
+        let mut nodes = self.par_degrees().enumerate().collect::<Vec<_>>();
+        nodes.par_sort_unstable_by_key(|(node, degree)| (std::cmp::Reverse(*degree), *node));
+        nodes.into_iter()
+    }
+
+    /// Returns the node with the highest out-degree as `(node_id, degree)`.
+    /// Ties are broken in favour of the lowest node id, and an empty graph yields `(0, 0)`.
+    pub fn max_degree(&self) -> (usize, usize) {
+        self.stats().max_degree
+    }
+
+    /// Returns the node with the lowest out-degree as `(node_id, degree)`.
+    /// Ties are broken in favour of the lowest node id, and an empty graph yields `(0, 0)`.
+    pub fn min_degree(&self) -> (usize, usize) {
+        self.stats().min_degree
+    }
+
+    /// Computes the common properties of the graph at once, with a single parallel pass over the vertex array.
+    pub fn stats(&self) -> GraphStats {
+        // The lowest id wins ties on both ends
+        let highest = |a: (usize, usize), b: (usize, usize)| {
+            if b.1 > a.1 || (b.1 == a.1 && b.0 < a.0) {
+                b
+            } else {
+                a
+            }
+        };
+        let lowest = |a: (usize, usize), b: (usize, usize)| {
+            if b.1 < a.1 || (b.1 == a.1 && b.0 < a.0) {
+                b
+            } else {
+                a
+            }
+        };
+
+        let extremes = self
+            .par_degrees()
+            .enumerate()
+            .map(|node| (node, node))
+            .reduce_with(|(max_a, min_a), (max_b, min_b)| {
+                (highest(max_a, max_b), lowest(min_a, min_b))
+            });
+        let (max_degree, min_degree) = extremes.unwrap_or(((0, 0), (0, 0)));
+
+        GraphStats {
+            n_nodes: self.n_nodes(),
+            n_edges: self.n_edges(),
+            max_degree,
+            min_degree,
+            avg_degree: self.avg_degree(),
+            density: self.density(),
+        }
+    }
+
+    /// Returns the average out-degree of the graph, or 0 if it has no nodes.
+    pub fn avg_degree(&self) -> f64 {
+        if self.n_nodes() == 0 {
+            return 0.0;
+        }
+
+        self.n_edges() as f64 / self.n_nodes() as f64
+    }
+
+    /// Returns the fraction of all possible edges between distinct nodes that exist, `|E| / (|V| * (|V| - 1))`,
+    /// or 0 if the graph has less than 2 nodes. Self loops and repeated edges are counted as well, so it can exceed 1.
+    pub fn density(&self) -> f64 {
+        let n_nodes = self.n_nodes() as f64;
+        if n_nodes < 2.0 {
+            return 0.0;
+        }
+
+        self.n_edges() as f64 / (n_nodes * (n_nodes - 1.0))
+    }
+
+    /// Returns whether the [density](Self::density) of the graph is below `threshold`.
+    pub fn is_sparse(&self, threshold: f64) -> bool {
+        self.density() < threshold
+    }
+
+    /// Same as [is_sparse](Self::is_sparse), with a threshold of [DEFAULT_SPARSITY_THRESHOLD].
+    pub fn is_sparse_default(&self) -> bool {
+        self.is_sparse(DEFAULT_SPARSITY_THRESHOLD)
+    }
+
+    /// Computes the in-degree of every node with a pass over the edge list.
+    fn in_degrees(&self) -> Vec<usize>
+    where
+        N: Send + Sync,
+    {
+        let counts = (0..self.n_nodes())
+            .map(|_| AtomicUsize::new(0))
+            .collect::<Vec<_>>();
+
+        self.edges.par_iter().for_each(|edge| {
+            counts[edge.as_()].fetch_add(1, Ordering::Relaxed);
+        });
+
+        counts.into_iter().map(AtomicUsize::into_inner).collect()
+    }
+
+    /// Returns the nodes without incoming edges, in increasing order.
+    /// Since the graph only stores outgoing edges, this requires a pass over the whole edge list to count the in-degrees.
+    pub fn find_source_nodes(&self) -> Vec<usize>
+    where
+        N: Send + Sync,
+    {
+        self.in_degrees()
+            .into_par_iter()
+            .enumerate()
+            .filter(|(_, in_degree)| *in_degree == 0)
+            .map(|(node, _)| node)
+            .collect()
+    }
+
+    /// Returns the nodes without outgoing edges, in increasing order.
+    pub fn find_sink_nodes(&self) -> Vec<usize> {
+        self.par_degrees()
+            .enumerate()
+            .filter(|(_, out_degree)| *out_degree == 0)
+            .map(|(node, _)| node)
+            .collect()
+    }
+
+    /// Returns the number of isolated nodes, i.e. nodes with both zero in-degree and zero out-degree.
+    /// Since the graph only stores outgoing edges, this requires an additional pass over the whole edge list.
+    /// See [n_zero_out_degree_nodes](Self::n_zero_out_degree_nodes) for a cheaper variant that ignores incoming edges.
+    pub fn n_isolated_nodes(&self) -> usize
+    where
+        N: Send + Sync,
+    {
+        self.par_degrees()
+            .zip(self.in_degrees())
+            .filter(|(out_degree, in_degree)| *out_degree == 0 && *in_degree == 0)
+            .count()
+    }
+
+    /// Returns the number of nodes with zero out-degree, regardless of their incoming edges.
+    pub fn n_zero_out_degree_nodes(&self) -> usize {
+        self.par_degrees().filter(|degree| *degree == 0).count()
+    }
+
+    /// Returns the edges of the subgraph induced by `nodes`, i.e. the edges whose endpoints are both in `nodes`, without writing any files.
+    /// The edges keep their original node ids and are yielded in increasing order of source. Nodes that do not exist are ignored.
+    pub fn subgraph_edges<'b>(
+        &'b self,
+        nodes: &'b HashSet<usize>,
+    ) -> impl Iterator<Item = (usize, usize)> + 'b {
+        let mut sources = nodes
+            .iter()
+            .copied()
+            .filter(|node| *node < self.n_nodes())
+            .collect::<Vec<_>>();
+        sources.sort_unstable();
+
+        sources.into_iter().flat_map(move |src| {
+            self.neighbors(src)
+                .iter()
+                .map(|dst| dst.as_())
+                .filter(|dst| nodes.contains(dst))
+                .map(move |dst| (src, dst))
+        })
+    }
+
+    /// Extracts the subgraph induced by `node_ids`, i.e. all the edges whose endpoints are both in `node_ids`, and stores it in `folder_name`.
+    /// `node_ids` must be sorted and free of duplicates. Node `node_ids[i]` is renamed to `i` in the new graph,
+    /// so the returned remapping table translates the new identifiers back to the original ones.
+    /// Returns `InvalidData` if `node_ids` is empty, is not sorted or contains nodes that do not exist.
+    pub fn induced_subgraph(
+        &self,
+        node_ids: &[usize],
+        folder_name: &str,
+    ) -> Result<(Graph<'a, N>, Vec<usize>), std::io::Error> {
+        if node_ids.windows(2).any(|w| w[0] >= w[1])
+            || node_ids.last().is_some_and(|last| *last >= self.n_nodes())
+        {
+            Err(std::io::ErrorKind::InvalidData)?;
+        }
+
+        let stream = node_ids.iter().enumerate().flat_map(|(src, node)| {
+            self.neighbors(*node).iter().filter_map(move |dst| {
+                let dst = node_ids.binary_search(&dst.as_()).ok()?;
+
+                Some(
+                    N::from_usize(src)
+                        .zip(N::from_usize(dst))
+                        .ok_or_else(|| std::io::ErrorKind::InvalidData.into()),
+                )
+            })
+        });
+
+        let graph = Graph::from_sorted_edges(stream, node_ids.len(), folder_name)?;
+
+        Ok((graph, node_ids.to_vec()))
+    }
+
+    /// Merges the edges of this graph and `other`, over the same node ids, into a new graph stored in `folder_name`.
+    /// Every edge present in either graph is kept once, so repeated edges are removed, and the edges of each node are sorted.
+    /// The new graph has as many nodes as the larger of the two.
+    pub fn union(
+        &self,
+        other: &Graph<N>,
+        folder_name: &str,
+    ) -> Result<Graph<'a, N>, std::io::Error> {
+        let n_nodes = self.n_nodes().max(other.n_nodes());
+
+        let stream = (0..n_nodes).flat_map(|src| {
+            let mut edges = [self, other]
+                .iter()
+                .filter(|graph| src < graph.n_nodes())
+                .flat_map(|graph| graph.neighbors(src).iter().copied())
+                .collect::<Vec<_>>();
+            edges.sort_unstable_by_key(|dst| dst.as_());
+            edges.dedup();
+
+            edges.into_iter().map(move |dst| {
+                N::from_usize(src)
+                    .map(|src| (src, dst))
+                    .ok_or_else(|| std::io::ErrorKind::InvalidData.into())
+            })
+        });
+
+        Graph::from_sorted_edges(stream, n_nodes, folder_name)
+    }
+
+    /// Extracts the largest [weakly connected component](algorithms::wcc) into `folder_name`, as an [induced subgraph](Self::induced_subgraph)
+    /// whose nodes keep their relative order. Ties between components of the same size go to the one with the lowest node id.
+    /// Returns the new graph, followed by the number of nodes and the number of edges that were discarded.
+    pub fn shrink_to_giant_component(
+        &self,
+        folder_name: &str,
+    ) -> Result<(Graph<'a, N>, usize, usize), std::io::Error>
+    where
+        N: Send + Sync,
+    {
+        let labels = algorithms::wcc(self);
+
+        let mut sizes = vec![0usize; self.n_nodes()];
+        for label in labels.iter() {
+            sizes[*label] += 1;
+        }
+        let giant = sizes
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(&a.0)))
+            .map(|(label, _)| label)
+            .unwrap_or(0);
+
+        let node_ids = (0..self.n_nodes())
+            .filter(|node| labels[*node] == giant)
+            .collect::<Vec<_>>();
+        let (graph, _) = self.induced_subgraph(&node_ids, folder_name)?;
+
+        let discarded_nodes = self.n_nodes() - graph.n_nodes();
+        let discarded_edges = self.n_edges() - graph.n_edges();
+        Ok((graph, discarded_nodes, discarded_edges))
+    }
+
+    /// Projects a bipartite graph onto one of its sides, and stores the projection in `folder_name`.
+    /// Two distinct nodes `a` and `b` of `side` are connected iff there is a path `a -> c -> b` through a node `c` of the other side,
+    /// so for graphs that store both directions of each edge, iff they share a neighbor. Repeated paths yield a single edge.
+    /// The nodes of the projection are renumbered from 0, so node `split + i` of the right side becomes node `i`.
+    /// Fails with `InvalidData` if `split` is larger than the number of nodes.
+    pub fn project_bipartite(
+        &self,
+        side: Side,
+        folder_name: &str,
+    ) -> Result<Graph<'a, N>, std::io::Error> {
+        let range = match side {
+            Side::Left(split) => 0..split,
+            Side::Right(split) => split..self.n_nodes(),
+        };
+        if range.start > range.end || range.end > self.n_nodes() {
+            Err(std::io::ErrorKind::InvalidData)?;
+        }
+        let n_nodes = range.len();
+        let offset = range.start;
+
+        // Every length-2 path through the other side, deduplicated per source
+        let stream = range.clone().flat_map(move |a| {
+            let mut projected = self
+                .neighbors(a)
+                .iter()
+                .map(|c| c.as_())
+                .filter(|c| !range.contains(c))
+                .flat_map(|c| self.neighbors(c).iter().map(|b| b.as_()))
+                .filter(|b| *b != a && range.contains(b))
+                .collect::<Vec<_>>();
+            projected.sort_unstable();
+            projected.dedup();
+
+            projected.into_iter().map(move |b| {
+                N::from_usize(a - offset)
+                    .zip(N::from_usize(b - offset))
+                    .ok_or_else(|| std::io::ErrorKind::InvalidData.into())
+            })
+        });
+
+        Graph::from_sorted_edges(stream, n_nodes, folder_name)
+    }
+
+    /// Returns the number of weakly connected components, counted with [union-find](algorithms::connectivity::connected_components_count).
+    pub fn connected_components_count(&self) -> usize {
+        algorithms::connectivity::connected_components_count(self)
+    }
+
+    /// Returns whether the graph has no directed cycle, i.e. whether it has a [topological order](algorithms::topological_sort).
+    /// The sort stops as soon as no more nodes can be released, so graphs where every node is in a cycle are rejected right away.
+    pub fn is_dag(&self) -> bool
+    where
+        N: Send + Sync,
+    {
+        algorithms::topological_sort(self).is_some()
+    }
+
+    /// Returns the nodes in the order they are discovered by a breadth-first search from `source`, so that `result[new_id] = old_id`.
+    /// Nodes that cannot be reached from `source` come after all reachable ones, discovered by further searches from the lowest unvisited node.
+    /// Renaming the nodes in this order tends to improve the cache locality of algorithms over the graph.
+    pub fn bfs_order(&self, source: usize) -> Vec<usize> {
+        let n_nodes = self.n_nodes();
+        let mut visited = vec![0u64; n_nodes.div_ceil(64)];
+        let mut order = Vec::with_capacity(n_nodes);
+        let mut queue = VecDeque::new();
+
+        for root in std::iter::once(source).chain(0..n_nodes) {
+            if visited[root / 64] & (1 << (root % 64)) != 0 {
+                continue;
+            }
+            visited[root / 64] |= 1 << (root % 64);
+            queue.push_back(root);
+
+            while let Some(node) = queue.pop_front() {
+                order.push(node);
+
+                for neighbor in self.neighbors(node).iter().map(|x| x.as_()) {
+                    if visited[neighbor / 64] & (1 << (neighbor % 64)) == 0 {
+                        visited[neighbor / 64] |= 1 << (neighbor % 64);
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+        }
+
+        order
+    }
+
+    /// Splits the nodes into `k` parts of at most `ceil(n_nodes / k)` nodes each, and returns the part of every node.
+    /// The nodes are visited in [BFS order](Self::bfs_order) from a node picked with `rng`, and each one is greedily assigned to the part that
+    /// already holds most of its neighbors, or to the smallest part if there is a tie. Only outgoing edges are considered,
+    /// so both directions of each edge should be stored for the best results. See [partition_quality](Self::partition_quality).
+    pub fn partition(&self, k: usize, rng: &mut impl Rng) -> Vec<usize> {
+        assert!(k > 0, "the graph must be split into at least one part");
+
+        let n_nodes = self.n_nodes();
+        if n_nodes == 0 {
+            return Vec::new();
+        }
+
+        let capacity = n_nodes.div_ceil(k);
+        let mut parts = vec![usize::MAX; n_nodes];
+        let mut sizes = vec![0; k];
+        let mut neighbors_in = vec![0; k];
+
+        for node in self.bfs_order(rng.gen_range(0..n_nodes)) {
+            neighbors_in.fill(0);
+            for neighbor in self.neighbors(node).iter().map(|x| x.as_()) {
+                if parts[neighbor] != usize::MAX {
+                    neighbors_in[parts[neighbor]] += 1;
+                }
+            }
+
+            let part = (0..k)
+                .filter(|part| sizes[*part] < capacity)
+                .max_by(|a, b| {
+                    neighbors_in[*a]
+                        .cmp(&neighbors_in[*b])
+                        .then(sizes[*b].cmp(&sizes[*a]))
+                        .then(b.cmp(a))
+                })
+                .unwrap();
+            parts[node] = part;
+            sizes[part] += 1;
+        }
+
+        parts
+    }
+
+    /// Returns the fraction of edges whose endpoints are in different parts of `labels`, e.g. as computed by [partition](Self::partition).
+    /// Returns 0 if the graph has no edges.
+    pub fn partition_quality(&self, labels: &[usize]) -> f64 {
+        if self.n_edges() == 0 {
+            return 0.0;
+        }
+
+        let cut = (0..self.n_nodes())
+            .map(|node| {
+                self.neighbors(node)
+                    .iter()
+                    .filter(|neighbor| labels[neighbor.as_()] != labels[node])
+                    .count()
+            })
+            .sum::<usize>();
+
+        cut as f64 / self.n_edges() as f64
+    }
+
+    /// Returns the sorted nodes reachable from `source` by following at most `k` edges.
+    /// `source` itself is only part of the result if `include_source` is set.
+    pub fn k_hop_neighborhood(&self, source: usize, k: u32, include_source: bool) -> Vec<usize> {
+        let mut visited = vec![false; self.n_nodes()];
+        visited[source] = true;
+        let mut frontier = vec![source];
+        let mut reached = Vec::new();
+
+        for _ in 0..k {
+            if frontier.is_empty() {
+                break;
+            }
+
+            let mut next = Vec::new();
+            for node in frontier {
+                for neighbor in self.neighbors(node).iter().map(|x| x.as_()) {
+                    if !visited[neighbor] {
+                        visited[neighbor] = true;
+                        next.push(neighbor);
+                    }
+                }
+            }
+            reached.extend_from_slice(&next);
+            frontier = next;
+        }
+
+        if include_source {
+            reached.push(source);
+        }
+        reached.sort_unstable();
+        reached
+    }
+
+    /// Renames the nodes in decreasing order of out-degree, with ties kept in their original order, and stores the new graph in `folder_name`.
+    /// The high-degree nodes, which dominate most computations, end up next to each other at the start of the arrays.
+    pub fn sorted_by_degree(&self, folder_name: &str) -> Result<Graph<'a, N>, std::io::Error> {
+        let mut order = (0..self.n_nodes()).collect::<Vec<_>>();
+        order.sort_by_key(|node| std::cmp::Reverse(self.degree(*node)));
+
+        let mut permutation = vec![0; order.len()];
+        for (new, old) in order.into_iter().enumerate() {
+            permutation[old] = new;
+        }
+
+        self.reorder(&permutation, folder_name)
+    }
+
+    /// Reverses the direction of every edge and stores the resulting graph in `folder_name`.
+    /// The reverse edge lists are built in memory with a counting sort by destination, so each of them ends up sorted.
+    pub fn transpose(&self, folder_name: &str) -> Result<Graph<'a, N>, std::io::Error>
+    where
+        N: Send + Sync,
+    {
+        let mut offsets = Vec::with_capacity(self.n_nodes() + 1);
+        offsets.push(0);
+        for degree in self.in_degrees() {
+            offsets.push(offsets.last().unwrap() + degree);
+        }
+
+        // Sources are visited in increasing order, so they are placed in order within each reverse edge list
+        let mut next = offsets.clone();
+        let mut sources = vec![N::zero(); self.n_edges()];
+        for node in 0..self.n_nodes() {
+            let src = N::from_usize(node).ok_or(std::io::ErrorKind::InvalidData)?;
+            for dst in self.neighbors(node).iter().map(|x| x.as_()) {
+                sources[next[dst]] = src;
+                next[dst] += 1;
+            }
+        }
+
+        Self::from_csr_arrays(&offsets, &sources, folder_name)
+    }
+
+    /// Renames every node according to `permutation`, where `permutation[old_id] = new_id`, and stores the new graph in `folder_name`.
+    /// The edge list of each node is sorted in the new graph.
+    /// Returns `InvalidData` if `permutation` is not a permutation of all the nodes of the graph.
+    pub fn reorder(
+        &self,
+        permutation: &[usize],
+        folder_name: &str,
+    ) -> Result<Graph<'a, N>, std::io::Error> {
+        if permutation.len() != self.n_nodes() {
+            Err(std::io::ErrorKind::InvalidData)?;
+        }
+
+        // The nodes are written in their new order, so the old node of each new one is needed
+        let mut inverse = vec![usize::MAX; permutation.len()];
+        for (old, new) in permutation.iter().enumerate() {
+            if *new >= inverse.len() || inverse[*new] != usize::MAX {
+                Err(std::io::ErrorKind::InvalidData)?;
+            }
+            inverse[*new] = old;
+        }
+
+        let stream = inverse.iter().enumerate().flat_map(|(src, old)| {
+            let mut edges = self
+                .neighbors(*old)
+                .iter()
+                .map(|dst| permutation[dst.as_()])
+                .collect::<Vec<_>>();
+            edges.sort_unstable();
+
+            edges.into_iter().map(move |dst| {
+                N::from_usize(src)
+                    .zip(N::from_usize(dst))
+                    .ok_or_else(|| std::io::ErrorKind::InvalidData.into())
+            })
+        });
+
+        Graph::from_sorted_edges(stream, self.n_nodes(), folder_name)
+    }
+
+    /// Writes the edges of the graph to `writer` as text, one `{src} {dst}` pair per line, sorted by source.
+    /// This is the format read by [from_txt_adjacency_list](Self::from_txt_adjacency_list).
+    /// Nodes without any edge do not appear in the output, so trailing isolated nodes are lost when reading it back.
+    pub fn write_txt_adjacency_list(&self, writer: &mut impl Write) -> std::io::Result<()> {
+        for (src, dst) in self.edge_iter() {
+            writeln!(writer, "{} {}", src, dst)?;
+        }
+        Ok(())
+    }
+
+    /// Same as [write_txt_adjacency_list](Self::write_txt_adjacency_list), except each edge is written as two serialized `N`,
+    /// as read by [from_binary_adjancency](Self::from_binary_adjancency).
+    pub fn write_binary_adjacency_list(&self, writer: &mut impl Write) -> std::io::Result<()> {
+        for (src, dst) in self.edge_iter() {
+            let src = N::from_usize(src).ok_or(std::io::ErrorKind::InvalidData)?;
+            writer.write_all(&src.serialize())?;
+            writer.write_all(&dst.serialize())?;
+        }
+        Ok(())
+    }
+
+    /// Writes the graph to `writer` in the Graphviz DOT format, which can be piped to `dot -Tpng`.
+    /// Only the first `max_nodes` nodes and the edges between them are written, to avoid huge outputs for large graphs.
+    pub fn to_dot(&self, max_nodes: usize, writer: &mut impl Write) -> std::io::Result<()> {
+        let n_nodes = self.n_nodes().min(max_nodes);
+
+        writeln!(writer, "digraph {{")?;
+        for node in 0..n_nodes {
+            writeln!(writer, "    {};", node)?;
+        }
+        for node in 0..n_nodes {
+            for edge in self.neighbors(node).iter().filter(|e| e.as_() < n_nodes) {
+                writeln!(writer, "    {} -> {};", node, edge)?;
+            }
+        }
+        writeln!(writer, "}}")
+    }
+
+    /// Same as [to_dot](Self::to_dot), except this time only the subgraph induced by `nodes` is written.
+    pub fn to_dot_subgraph(&self, nodes: &[usize], writer: &mut impl Write) -> std::io::Result<()> {
+        let included = nodes.iter().copied().collect::<HashSet<usize>>();
+
+        writeln!(writer, "digraph {{")?;
+        for node in nodes {
+            writeln!(writer, "    {};", node)?;
+        }
+        for &node in nodes {
+            for edge in self
+                .neighbors(node)
+                .iter()
+                .filter(|e| included.contains(&e.as_()))
+            {
+                writeln!(writer, "    {} -> {};", node, edge)?;
+            }
+        }
+        writeln!(writer, "}}")
+    }
+}
+
+/// One of the two sides of a bipartite graph whose nodes `0..split` form one side and `split..n_nodes` the other,
+/// as used by [Graph::project_bipartite].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Side {
+    /// The nodes `0..split`.
+    Left(usize),
+    /// The nodes `split..n_nodes`.
+    Right(usize),
+}
+
+/// The common properties of a graph, as computed by [Graph::stats].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GraphStats {
+    /// The number of nodes, see [Graph::n_nodes].
+    pub n_nodes: usize,
+    /// The number of edges, see [Graph::n_edges].
+    pub n_edges: usize,
+    /// The node with the highest out-degree as `(node_id, degree)`, see [Graph::max_degree].
+    pub max_degree: (usize, usize),
+    /// The node with the lowest out-degree as `(node_id, degree)`, see [Graph::min_degree].
+    pub min_degree: (usize, usize),
+    /// The average out-degree, see [Graph::avg_degree].
+    pub avg_degree: f64,
+    /// The density, see [Graph::density].
+    pub density: f64,
+}
+
+/// Iterates over a [Graph] struct and yields the outgoing edge lists of type `&[N]` for each node.
+pub struct GraphIterator<'a, N> {
+    nodes: &'a [usize],
+    edges: &'a [N],
+    current_node: usize,
+}
+
+impl<'a, N> GraphIterator<'a, N> {
+    /// Moves the iterator to `node_id` in constant time, so that the next edge list yielded is the one of `node_id`.
+    /// Moving to `n_nodes` exhausts the iterator. Panics if `node_id` is greater than the number of nodes.
+    pub fn skip_to(&mut self, node_id: usize) -> &mut Self {
+        assert!(
+            node_id < self.nodes.len(),
+            "node {} is out of bounds for a graph with {} nodes",
+            node_id,
+            self.nodes.len() - 1
+        );
+
+        self.current_node = node_id;
+        self
+    }
+}
+
+impl<'a, N> Iterator for GraphIterator<'a, N>
+where
+    N: ValidGraphType,
+{
+    type Item = &'a [N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.current_node >= self.nodes.len() - 1 {
+            return None;
+        };
+
+        let start = self.nodes[self.current_node];
+        let end = self.nodes[self.current_node + 1];
+
+        self.current_node += 1;
+
+        Some(&self.edges[start..end])
+    }
+}
+
+/// Iterates in parallel over the nodes of a [GraphIterator] and yields `(node, edge list)` pairs.
+/// This is a separate type so that [GraphIterator] does not have both sequential and parallel adapters with the same names.
+struct ParGraphIterator<'a, N>(GraphIterator<'a, N>);
+
+impl<'a, N> ParallelIterator for ParGraphIterator<'a, N>
+where
+    N: ValidGraphType + Send + Sync,
+{
+    type Item = (usize, &'a [N]);
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: rayon::iter::plumbing::UnindexedConsumer<Self::Item>,
+    {
+        let GraphIterator { nodes, edges, .. } = self.0;
+
+        nodes[..nodes.len() - 1]
+            .par_iter()
+            .enumerate()
+            .zip(nodes[1..].par_iter())
+            .map(|((idx, start), end)| {
                 let start = *start;
                 let end = *end;
-                (idx, &self.edges[start..end])
+                (idx, &edges[start..end])
             })
             .drive_unindexed(consumer)
     }
-}
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::io::{BufWriter, Write};
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    fn get_graph<'a, T>(edge_list: &[(T, T)]) -> Graph<'a, T>
+    where
+        T: ValidGraphType,
+    {
+        // Generate random filename
+        let destination_folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+
+        Graph::<T>::from_adjacency_list(
+            edge_list.iter().map(|edge| Ok(*edge)),
+            destination_folder_name.as_str(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn parse_from_file() {
+        let edges = vec![(0u32, 1u32), (0, 2), (1, 5), (1, 2), (4, 7)];
+
+        let expected_nodes = vec![0usize, 2, 4, 4, 4, 5, 5, 5, 5];
+        let expected_edges = vec![1u32, 2, 2, 5, 7];
+
+        let source_file_name = format!("/tmp/tmp_src_{}", rand::random::<u32>());
+        let destination_folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+
+        let file = fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&source_file_name)
+            .unwrap();
+
+        // Write edges to file
+        let mut writer = BufWriter::new(&file);
+        for edge in edges {
+            let line = format!("{} {}\n", edge.0, edge.1);
+            writer.write_all(line.as_bytes()).unwrap();
+        }
+
+        drop(writer);
+
+        let file = fs::OpenOptions::new()
+            .read(true)
+            .open(&source_file_name)
+            .unwrap();
+
+        let graph = match Graph::<u32>::from_txt_adjacency_list(file, &destination_folder_name) {
+            Ok(graph) => graph,
+            Err(e) => panic!("{:?}", e),
+        };
+
+        // Check correctness
+        assert_eq!(
+            graph.iterate_nodes().collect::<Vec<usize>>(),
+            expected_nodes
+        );
+        assert_eq!(graph.iterate_edges().collect::<Vec<u32>>(), expected_edges);
+    }
+
+    #[test]
+    fn parse_from_csv() {
+        let csv = "src;dst;weight\n0;1;3\n0; 2;1\n\n1;5\n1;2;7\n4 ;7\n";
+
+        let expected_nodes = vec![0usize, 2, 4, 4, 4, 5, 5, 5, 5];
+        let expected_edges = vec![1u32, 2, 2, 5, 7];
+
+        let destination_folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+
+        let graph =
+            match Graph::<u32>::from_csv(csv.as_bytes(), ';', true, &destination_folder_name) {
+                Ok(graph) => graph,
+                Err(e) => panic!("{:?}", e),
+            };
+
+        assert_eq!(
+            graph.iterate_nodes().collect::<Vec<usize>>(),
+            expected_nodes
+        );
+        assert_eq!(graph.iterate_edges().collect::<Vec<u32>>(), expected_edges);
+    }
+
+    #[test]
+    fn parse_from_matrix_market() {
+        let mtx = "%%MatrixMarket matrix coordinate real general\n\
+                   % a comment\n\
+                   8 8 5\n\
+                   2 6 0.5\n\
+                   1 2 1.0\n\
+                   1 3 2.0\n\
+                   2 3 1.5\n\
+                   5 8 3.0\n";
+
+        let expected_nodes = vec![0usize, 2, 4, 4, 4, 5, 5, 5, 5];
+        let expected_edges = vec![1u32, 2, 2, 5, 7];
+
+        let destination_folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+
+        let graph = match Graph::<u32>::from_matrix_market(mtx.as_bytes(), &destination_folder_name)
+        {
+            Ok(graph) => graph,
+            Err(e) => panic!("{:?}", e),
+        };
+
+        assert_eq!(
+            graph.iterate_nodes().collect::<Vec<usize>>(),
+            expected_nodes
+        );
+        assert_eq!(graph.iterate_edges().collect::<Vec<u32>>(), expected_edges);
+    }
+
+    #[test]
+    fn matrix_market_keeps_empty_rows() {
+        let mtx = "%%MatrixMarket matrix coordinate pattern general\n\
+                   3 3 1\n\
+                   1 2\n";
+
+        let destination_folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+        let graph =
+            Graph::<u32>::from_matrix_market(mtx.as_bytes(), &destination_folder_name).unwrap();
+
+        assert_eq!(graph.n_nodes(), 3);
+        assert_eq!(
+            graph.iter().map(|edges| edges.to_vec()).collect::<Vec<_>>(),
+            vec![vec![1u32], vec![], vec![]]
+        );
+
+        // The number of entries in the header is not trusted for the allocation
+        let mtx = "%%MatrixMarket matrix coordinate pattern symmetric\n\
+                   3 3 18446744073709551615\n\
+                   1 2\n";
+        let graph = Graph::<u32>::from_matrix_market(
+            mtx.as_bytes(),
+            &format!("/tmp/tmp_dst_{}", rand::random::<u32>()),
+        )
+        .unwrap();
+        assert_eq!(graph.n_edges(), 2);
+    }
+
+    #[test]
+    fn parse_from_symmetric_matrix_market() {
+        let mtx = "%%MatrixMarket matrix coordinate pattern symmetric\n\
+                   3 3 3\n\
+                   2 1\n\
+                   3 2\n\
+                   3 3\n";
+
+        let expected_res = vec![vec![1u32], vec![0, 2], vec![1, 2]];
+
+        let destination_folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+
+        let graph = match Graph::<u32>::from_matrix_market(mtx.as_bytes(), &destination_folder_name)
+        {
+            Ok(graph) => graph,
+            Err(e) => panic!("{:?}", e),
+        };
+
+        assert_eq!(
+            graph.iter().map(|edges| edges.to_vec()).collect::<Vec<_>>(),
+            expected_res
+        );
+    }
+
+    #[test]
+    fn parse_from_dimacs_text() {
+        let dimacs = "c sample road network\n\
+                      p sp 8 5\n\
+                      a 2 6 4\n\
+                      a 1 2 10\n\
+                      a 1 3 3\n\
+                      c unsorted arcs are fine\n\
+                      a 2 3 7\n\
+                      a 5 8 1\n";
+
+        let expected_nodes = vec![0usize, 2, 4, 4, 4, 5, 5, 5, 5];
+        let expected_edges = vec![1u32, 2, 2, 5, 7];
+
+        let destination_folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+
+        let graph =
+            match Graph::<u32>::from_dimacs_text(dimacs.as_bytes(), &destination_folder_name) {
+                Ok(graph) => graph,
+                Err(e) => panic!("{:?}", e),
+            };
+
+        assert_eq!(
+            graph.iterate_nodes().collect::<Vec<usize>>(),
+            expected_nodes
+        );
+        assert_eq!(graph.iterate_edges().collect::<Vec<u32>>(), expected_edges);
+    }
+
+    #[test]
+    fn parse_from_pajek() {
+        let pajek = "% a small social network\n\
+                     *Vertices 6\n\
+                     1 \"alice\" 0.1 0.2\n\
+                     2 \"bob\"\n\
+                     3 \"carol\"\n\
+                     4 \"dave\"\n\
+                     5 \"erin\"\n\
+                     6 \"frank\"\n\
+                     *Arcs\n\
+                     2 1 1.5\n\
+                     1 3\n\
+                     *Edges\n\
+                     4 2 2\n\
+                     *Arcslist\n\
+                     5 1 2 3\n";
+
+        let destination_folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+        let graph = Graph::<u32>::from_pajek(pajek.as_bytes(), &destination_folder_name).unwrap();
+
+        assert_eq!(
+            graph.iter().map(|e| e.to_vec()).collect::<Vec<_>>(),
+            vec![vec![2], vec![0, 3], vec![], vec![1], vec![0, 1, 2], vec![]]
+        );
+
+        for invalid in ["1 2\n", "*Vertices 2\n*Arcs\n1 3\n", "*Arcs\n1 2\n"] {
+            assert!(Graph::<u32>::from_pajek(
+                invalid.as_bytes(),
+                &format!("/tmp/tmp_dst_{}", rand::random::<u32>())
+            )
+            .is_err());
+        }
+    }
+
+    #[test]
+    fn parse_from_pajek_network() {
+        let pajek = "*Network karate\n\
+                     *Vertices 3\n\
+                     1 \"a\"\n\
+                     *Arcs\n\
+                     1 2 0.5\n\
+                     *Partition groups\n\
+                     *Vertices 3\n\
+                     1\n\
+                     2\n\
+                     *Edges\n\
+                     2 3\n";
+
+        let graph = Graph::<u32>::from_pajek(
+            pajek.as_bytes(),
+            &format!("/tmp/tmp_dst_{}", rand::random::<u32>()),
+        )
+        .unwrap();
+
+        assert_eq!(
+            graph.iter().map(|e| e.to_vec()).collect::<Vec<_>>(),
+            vec![vec![1], vec![2], vec![1]]
+        );
+
+        // Weights are dropped, but must still be numbers
+        assert!(Graph::<u32>::from_pajek(
+            "*Vertices 2\n*Arcs\n1 2 heavy\n".as_bytes(),
+            &format!("/tmp/tmp_dst_{}", rand::random::<u32>())
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn parse_from_ligra_binary() {
+        let write = |values: &[u64]| {
+            let file_name = format!("/tmp/tmp_ligra_{}.adj", rand::random::<u32>());
+            let bytes = values
+                .iter()
+                .flat_map(|v| v.to_ne_bytes())
+                .collect::<Vec<_>>();
+            std::fs::write(&file_name, bytes).unwrap();
+            file_name
+        };
+
+        // 4 nodes and 5 edges
+        let adj_file = write(&[4, 5, 0, 2, 4, 4, 1, 2, 2, 3, 0]);
+        let destination_folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+        let graph = Graph::<u32>::from_ligra_binary(&adj_file, &destination_folder_name).unwrap();
+
+        assert_eq!(
+            graph.iter().map(|e| e.to_vec()).collect::<Vec<_>>(),
+            vec![vec![1, 2], vec![2, 3], vec![], vec![0]]
+        );
+
+        // Truncated files, offsets out of order and edges to missing nodes
+        for invalid in [
+            &[4u64, 5, 0, 2, 4, 4, 1, 2, 2, 3][..],
+            &[4, 5, 0, 4, 2, 4, 1, 2, 2, 3, 0],
+            &[4, 5, 0, 2, 4, 4, 1, 2, 2, 3, 4],
+        ] {
+            assert!(Graph::<u32>::from_ligra_binary(
+                &write(invalid),
+                &format!("/tmp/tmp_dst_{}", rand::random::<u32>())
+            )
+            .is_err());
+        }
+    }
+
+    #[test]
+    fn dimacs_keeps_isolated_nodes() {
+        let dimacs = "p sp 5 1\na 1 2 7\n";
+
+        let graph = Graph::<u32>::from_dimacs_text(
+            dimacs.as_bytes(),
+            &format!("/tmp/tmp_dst_{}", rand::random::<u32>()),
+        )
+        .unwrap();
+        assert_eq!(graph.n_nodes(), 5);
+        assert_eq!(graph.neighbors(0), &[1]);
+        assert_eq!(graph.degree(4), 0);
+
+        let bytes = [reading::DIMACS_SENTINEL, 1, 2, 7]
+            .iter()
+            .flat_map(|x| x.to_ne_bytes())
+            .collect::<Vec<u8>>();
+
+        let graph = Graph::<u32>::from_dimacs_binary_with_n_nodes(
+            bytes.as_slice(),
+            5,
+            &format!("/tmp/tmp_dst_{}", rand::random::<u32>()),
+        )
+        .unwrap();
+        assert_eq!(graph.n_nodes(), 5);
+        assert_eq!(graph.neighbors(0), &[1]);
+
+        assert!(Graph::<u32>::from_dimacs_binary_with_n_nodes(
+            bytes.as_slice(),
+            1,
+            &format!("/tmp/tmp_dst_{}", rand::random::<u32>())
+        )
+        .is_err());
+
+        // The number of arcs in the problem line is not trusted for the allocation
+        let dimacs = "p sp 5 18446744073709551615\na 1 2 7\n";
+        let graph = Graph::<u32>::from_dimacs_text(
+            dimacs.as_bytes(),
+            &format!("/tmp/tmp_dst_{}", rand::random::<u32>()),
+        )
+        .unwrap();
+        assert_eq!(graph.n_edges(), 1);
+    }
+
+    #[test]
+    fn parse_from_dimacs_binary() {
+        let records = [
+            [reading::DIMACS_SENTINEL, 2, 6, 4],
+            [reading::DIMACS_SENTINEL, 1, 2, 10],
+            [reading::DIMACS_SENTINEL, 1, 3, 3],
+            [reading::DIMACS_SENTINEL, 2, 3, 7],
+            [reading::DIMACS_SENTINEL, 5, 8, 1],
+        ];
+        let expected_nodes = vec![0usize, 2, 4, 4, 4, 5, 5, 5, 5];
+        let expected_edges = vec![1u32, 2, 2, 5, 7];
+
+        let bytes = records
+            .iter()
+            .flatten()
+            .flat_map(|x| x.to_ne_bytes())
+            .collect::<Vec<u8>>();
+
+        let destination_folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+
+        let graph =
+            match Graph::<u32>::from_dimacs_binary(bytes.as_slice(), &destination_folder_name) {
+                Ok(graph) => graph,
+                Err(e) => panic!("{:?}", e),
+            };
+
+        assert_eq!(
+            graph.iterate_nodes().collect::<Vec<usize>>(),
+            expected_nodes
+        );
+        assert_eq!(graph.iterate_edges().collect::<Vec<u32>>(), expected_edges);
+
+        // A corrupted sentinel must be rejected
+        let mut corrupted = bytes.clone();
+        corrupted[16] = 0;
+        assert!(Graph::<u32>::from_dimacs_binary(
+            corrupted.as_slice(),
+            &format!("/tmp/tmp_dst_{}", rand::random::<u32>())
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn construction_hint() {
+        let edges = [(0u32, 1u32), (0, 2), (1, 5), (1, 2), (4, 7)];
+        let expected_nodes = vec![0usize, 2, 4, 4, 4, 5, 5, 5, 5];
+
+        let graph = Graph::<u32>::from_adjacency_list_with_hint(
+            edges.iter().map(|x| Ok(*x)),
+            &format!("/tmp/tmp_dst_{}", rand::random::<u32>()),
+            ConstructionHint::TrustSorted,
+        )
+        .unwrap();
+        assert_eq!(
+            graph.iterate_nodes().collect::<Vec<usize>>(),
+            expected_nodes
+        );
+
+        // Unsorted lists are still rejected by default
+        assert!(Graph::<u32>::from_adjacency_list_with_hint(
+            edges.iter().rev().map(|x| Ok(*x)),
+            &format!("/tmp/tmp_dst_{}", rand::random::<u32>()),
+            ConstructionHint::CheckSorted,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn csr_arrays_round_trip() {
+        let nodes = [0usize, 2, 4, 4, 4, 5, 5, 5, 5];
+        let edges = [1u32, 2, 5, 2, 7];
+
+        let graph = Graph::<u32>::from_csr_arrays(
+            &nodes,
+            &edges,
+            &format!("/tmp/tmp_dst_{}", rand::random::<u32>()),
+        )
+        .unwrap();
+
+        assert_eq!(graph.iterate_nodes().collect::<Vec<usize>>(), nodes);
+        assert_eq!(graph.iterate_edges().collect::<Vec<u32>>(), edges);
+
+        let invalid: [(&[usize], &[u32]); 4] = [
+            (&[0], &[]),
+            (&[0, 2, 1, 3], &[0, 1, 2]),
+            (&[0, 1, 2], &[0]),
+            (&[0, 1, 2], &[0, 2]),
+        ];
+        for (nodes, edges) in invalid {
+            assert!(Graph::<u32>::from_csr_arrays(
+                nodes,
+                edges,
+                &format!("/tmp/tmp_dst_{}", rand::random::<u32>())
+            )
+            .is_err());
+        }
+    }
+
+    #[test]
+    fn in_memory_graph() {
+        let graph =
+            Graph::<u32>::from_csr_vecs(vec![0, 2, 4, 4, 4, 5, 5, 5, 5], vec![1, 2, 5, 2, 7])
+                .unwrap();
+
+        assert_eq!(graph.n_nodes(), 8);
+        assert_eq!(graph.n_edges(), 5);
+        assert_eq!(graph.neighbors(1), &[5, 2]);
+        assert_eq!(graph.par_iter().filter(|(_, e)| e.is_empty()).count(), 5);
+
+        assert!(Graph::<u32>::from_csr_vecs(vec![0, 1], vec![1]).is_err());
+    }
+
+    #[test]
+    fn export_csr_arrays() {
+        let graph = get_graph(&[(0u32, 1u32), (0, 2), (1, 5), (1, 2), (4, 7)]);
+
+        let (nodes, edges) = graph.as_csr_slices();
+        assert_eq!(nodes, &[0, 2, 4, 4, 4, 5, 5, 5, 5]);
+        assert_eq!(edges, &[1, 2, 2, 5, 7]);
+
+        let (nodes, edges) = graph.to_csr_arrays();
+        let copy = Graph::from_csr_vecs(nodes, edges).unwrap();
+        assert_eq!(copy.as_csr_slices(), graph.as_csr_slices());
+    }
+
+    #[test]
+    fn parse_from_binary() {
+        let edges = [(0u32, 1u32), (0, 2), (1, 5), (1, 2), (4, 7)];
+        let expected_nodes = vec![0usize, 2, 4, 4, 4, 5, 5, 5, 5];
+        let expected_edges = vec![1u32, 2, 2, 5, 7];
+
+        let destionation_folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+
+        let edges_flatten = edges
+            .iter()
+            .flat_map(|x| vec![x.0, x.1])
+            .collect::<Vec<u32>>();
+
+        // Transform into slice of u8 for Read
+        let edges_flatten = unsafe {
+            std::slice::from_raw_parts(
+                edges_flatten.as_ptr() as *const u8,
+                edges_flatten.len() * std::mem::size_of::<u32>(),
+            )
+        };
+
+        let graph =
+            match Graph::<u32>::from_binary_adjancency(edges_flatten, &destionation_folder_name) {
+                Ok(graph) => graph,
+                Err(e) => panic!("{:?}", e),
+            };
+
+        // Check correctness
+        assert_eq!(
+            graph.iterate_nodes().collect::<Vec<usize>>(),
+            expected_nodes
+        );
+
+        assert_eq!(graph.iterate_edges().collect::<Vec<u32>>(), expected_edges);
+    }
+
+    #[test]
+    fn parse_from_binary_with_endianness() {
+        let edges = [(0u32, 1u32), (0, 2), (1, 5), (1, 2), (4, 7)];
+
+        for (endianness, to_bytes) in [
+            (
+                util::Endianness::Big,
+                u32::to_be_bytes as fn(u32) -> [u8; 4],
+            ),
+            (util::Endianness::Little, u32::to_le_bytes),
+        ] {
+            let bytes = edges
+                .iter()
+                .flat_map(|x| [to_bytes(x.0), to_bytes(x.1)])
+                .flatten()
+                .collect::<Vec<u8>>();
+            let destination_folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+
+            let graph = Graph::<u32>::from_binary_adjancency_with_endianness(
+                bytes.as_slice(),
+                &destination_folder_name,
+                endianness,
+            )
+            .unwrap();
+
+            assert_eq!(
+                graph.iterate_nodes().collect::<Vec<usize>>(),
+                vec![0usize, 2, 4, 4, 4, 5, 5, 5, 5]
+            );
+            assert_eq!(
+                graph.iterate_edges().collect::<Vec<u32>>(),
+                vec![1u32, 2, 2, 5, 7]
+            );
+        }
+    }
+
+    #[test]
+    fn parse_from_general_stream() {
+        let edges = [(0u32, 1u32), (0, 2), (1, 5), (1, 2), (4, 7)];
+
+        let expected_nodes = vec![0usize, 2, 4, 4, 4, 5, 5, 5, 5];
+        let expected_edges = vec![1u32, 2, 2, 5, 7];
+
+        let destination_folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+
+        // Read from string bytes stream
+        let graph = match Graph::<u32>::from_adjacency_list(
+            edges.iter().map(|x| Ok(*x)),
+            &destination_folder_name,
+        ) {
+            Ok(graph) => graph,
+            Err(e) => panic!("{:?}", e),
+        };
+
+        println!("Destionation folder: {}", destination_folder_name);
+
+        assert_eq!(
+            graph.iterate_nodes().collect::<Vec<usize>>(),
+            expected_nodes
+        );
+        assert_eq!(graph.iterate_edges().collect::<Vec<u32>>(), expected_edges);
+    }
+
+    #[test]
+    fn highest_node_only_as_source() {
+        let graph = get_graph(&[(0u32, 1u32), (5, 0)]);
+
+        assert_eq!(graph.n_nodes(), 6);
+        assert_eq!(graph.n_edges(), 2);
+        assert_eq!(
+            graph.iterate_nodes().collect::<Vec<usize>>(),
+            vec![0, 1, 1, 1, 1, 1, 2]
+        );
+        assert_eq!(graph.neighbors(5), &[0]);
+    }
+
+    #[test]
+    fn load_u64_graph() {
+        let edges = [(0u64, 1u64), (0, 2), (1, 5), (1, 2), (4, 7)];
+
+        let expected_nodes = vec![0usize, 2, 4, 4, 4, 5, 5, 5, 5];
+        let expected_edges = vec![1u64, 2, 2, 5, 7];
+
+        let destination_folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+
+        let graph = match Graph::<u64>::from_adjacency_list(
+            edges.iter().map(|x| Ok(*x)),
+            &destination_folder_name,
+        ) {
+            Ok(graph) => graph,
+            Err(e) => panic!("{:?}", e),
+        };
+
+        assert_eq!(
+            graph.iterate_nodes().collect::<Vec<usize>>(),
+            expected_nodes
+        );
+
+        assert_eq!(graph.iterate_edges().collect::<Vec<u64>>(), expected_edges);
+    }
+
+    #[test]
+    fn test_graph_load() {
+        let edges = [(0u32, 1u32), (0, 2), (1, 5), (1, 2), (4, 7)];
+        let expected_nodes = vec![0usize, 2, 4, 4, 4, 5, 5, 5, 5];
+        let expected_edges = vec![1u32, 2, 2, 5, 7];
+
+        let destination_folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+
+        match Graph::<u32>::from_adjacency_list(
+            edges.iter().map(|x| Ok(*x)),
+            &destination_folder_name,
+        ) {
+            Ok(_) => {}
+            Err(e) => panic!("{:?}", e),
+        };
+
+        // Load graph from memory
+        let graph = match Graph::<u32>::load_graph(&destination_folder_name) {
+            Ok(graph) => graph,
+            Err(e) => panic!("{:?}", e),
+        };
+
+        assert_eq!(
+            graph.iterate_nodes().collect::<Vec<usize>>(),
+            expected_nodes
+        );
+
+        assert_eq!(graph.iterate_edges().collect::<Vec<u32>>(), expected_edges);
+    }
+
+    #[test]
+    fn dot_export() {
+        let graph = get_graph(&[(0u32, 1u32), (0, 2), (1, 5), (1, 2), (4, 7)]);
+
+        let mut output = Vec::new();
+        graph.to_dot(3, &mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "digraph {\n    0;\n    1;\n    2;\n    0 -> 1;\n    0 -> 2;\n    1 -> 2;\n}\n"
+        );
+
+        let mut output = Vec::new();
+        graph.to_dot_subgraph(&[1, 4, 5, 7], &mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "digraph {\n    1;\n    4;\n    5;\n    7;\n    1 -> 5;\n    4 -> 7;\n}\n"
+        );
+    }
+
+    #[test]
+    fn adjacency_list_export() {
+        let graph = get_graph(&[(0u32, 1u32), (0, 2), (1, 2), (1, 5), (4, 7)]);
+
+        let mut output = Vec::new();
+        graph.write_txt_adjacency_list(&mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output.clone()).unwrap(),
+            "0 1\n0 2\n1 2\n1 5\n4 7\n"
+        );
+        let txt = Graph::<u32>::from_txt_adjacency_list(
+            output.as_slice(),
+            &format!("/tmp/tmp_dst_{}", rand::random::<u32>()),
+        )
+        .unwrap();
+        assert_eq!(txt.as_csr_slices(), graph.as_csr_slices());
+
+        let mut output = Vec::new();
+        graph.write_binary_adjacency_list(&mut output).unwrap();
+        assert_eq!(output.len(), 5 * 2 * std::mem::size_of::<u32>());
+        let binary = Graph::<u32>::from_binary_adjancency(
+            output.as_slice(),
+            &format!("/tmp/tmp_dst_{}", rand::random::<u32>()),
+        )
+        .unwrap();
+        assert_eq!(binary.as_csr_slices(), graph.as_csr_slices());
+    }
+
+    #[test]
+    fn is_dag() {
+        let tree = get_graph(&[(0u32, 1u32), (0, 2), (1, 3), (1, 4), (2, 5)]);
+        assert!(tree.is_dag());
+
+        let diamond = get_graph(&[(0u32, 1u32), (0, 2), (1, 3), (2, 3)]);
+        assert!(diamond.is_dag());
+
+        let cycle = get_graph(&[(0u32, 1u32), (1, 2), (2, 0)]);
+        assert!(!cycle.is_dag());
+
+        let tail_cycle = get_graph(&[(0u32, 1u32), (1, 2), (2, 3), (3, 1)]);
+        assert!(!tail_cycle.is_dag());
+
+        let self_loop = get_graph(&[(0u32, 1u32), (1, 1)]);
+        assert!(!self_loop.is_dag());
+    }
+
+    #[test]
+    fn self_loops() {
+        let graph = get_graph(&[(0u32, 1u32), (1, 1), (1, 2), (3, 3), (3, 3), (4, 0)]);
+        assert!(graph.has_self_loops());
+        assert_eq!(graph.self_loop_nodes(), vec![1, 3]);
+
+        let graph = get_graph(&[(0u32, 1u32), (1, 0), (1, 2)]);
+        assert!(!graph.has_self_loops());
+        assert!(graph.self_loop_nodes().is_empty());
+    }
+
+    #[test]
+    fn adjacency_matrix() {
+        let matrix = vec![
+            vec![false, true, true, false],
+            vec![false, false, true, false],
+            vec![true, false, true, false],
+            vec![false, false, false, false],
+        ];
+        let graph = Graph::<u32>::from_adjacency_matrix(
+            &matrix,
+            &format!("/tmp/tmp_dst_{}", rand::random::<u32>()),
+        )
+        .unwrap();
+
+        assert_eq!(graph.n_nodes(), 4);
+        assert_eq!(
+            graph.iter().map(|x| x.to_vec()).collect::<Vec<_>>(),
+            vec![vec![1, 2], vec![2], vec![0, 2], vec![]]
+        );
+
+        let not_square = vec![vec![false, true], vec![true]];
+        let error = Graph::<u32>::from_adjacency_matrix(
+            not_square,
+            &format!("/tmp/tmp_dst_{}", rand::random::<u32>()),
+        )
+        .err()
+        .unwrap();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn custom_file_names() {
+        let folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+        let forward = vec![(0u32, 1u32), (1, 2)];
+        let reverse = vec![(1u32, 0u32), (2, 1)];
+
+        Graph::<u32>::from_adjacency_list(forward.into_iter().map(Ok), &folder_name).unwrap();
+        Graph::<u32>::with_custom_file_names("rev_vertex.csr", "rev_edge.csr")
+            .build_from_iter(reverse.into_iter().map(Ok), &folder_name)
+            .unwrap();
+
+        // Both graphs live in the same folder, and the sidecar of the default one is left alone
+        let forward = Graph::<u32>::load_graph(&folder_name).unwrap();
+        let reverse = Graph::<u32>::load_graph_with_file_names(
+            &folder_name,
+            "rev_vertex.csr",
+            "rev_edge.csr",
+        )
+        .unwrap();
+        assert_eq!(forward.neighbors(0), &[1]);
+        assert_eq!(reverse.neighbors(2), &[1]);
+        assert_eq!(reverse.neighbors(0), &[] as &[u32]);
+
+        let default =
+            Graph::<u32>::load_graph_with_file_names(&folder_name, "vertex.csr", "edge.csr")
+                .unwrap();
+        assert_eq!(default.as_csr_slices(), forward.as_csr_slices());
+
+        assert_eq!(
+            Graph::<u32>::load_graph_with_file_names(&folder_name, "missing.csr", "rev_edge.csr")
+                .err()
+                .unwrap()
+                .kind(),
+            std::io::ErrorKind::NotFound
+        );
+    }
+
+    #[test]
+    fn memory_usage() {
+        let graph = get_graph(&[(0u64, 1u64), (0, 2), (1, 5), (1, 2), (4, 7)]);
+        let usize_bytes = std::mem::size_of::<usize>();
+
+        assert_eq!(graph.memory_usage(), (9 * usize_bytes, 5 * 8));
+        assert_eq!(graph.total_memory_bytes(), 9 * usize_bytes + 40);
+
+        let in_memory = Graph::<u32>::from_csr_vecs(vec![0, 1, 1], vec![1]).unwrap();
+        assert_eq!(in_memory.memory_usage(), (3 * usize_bytes, 4));
+    }
+
+    #[test]
+    fn source_and_sink_nodes() {
+        let graph = get_graph(&[(0u32, 1u32), (0, 2), (1, 5), (1, 2), (4, 7)]);
+
+        assert_eq!(graph.find_source_nodes(), vec![0, 3, 4, 6]);
+        assert_eq!(graph.find_sink_nodes(), vec![2, 3, 5, 6, 7]);
+
+        let cycle = get_graph(&[(0u32, 1u32), (1, 2), (2, 0)]);
+        assert!(cycle.find_source_nodes().is_empty());
+        assert!(cycle.find_sink_nodes().is_empty());
+    }
+
+    #[test]
+    fn project_bipartite() {
+        // Users 0..3 and items 3..6, with both directions of each edge
+        let mut edges = vec![(0u32, 3u32), (0, 4), (1, 3), (1, 4), (2, 5)];
+        edges.extend(edges.clone().into_iter().map(|(a, b)| (b, a)));
+        edges.sort_unstable();
+        let graph = get_graph(&edges);
+
+        let folder = || format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+
+        // Users 0 and 1 share both items 3 and 4, which becomes a single edge
+        let users = graph.project_bipartite(Side::Left(3), &folder()).unwrap();
+        assert_eq!(
+            users.iter().map(|x| x.to_vec()).collect::<Vec<_>>(),
+            vec![vec![1], vec![0], vec![]]
+        );
+
+        let items = graph.project_bipartite(Side::Right(3), &folder()).unwrap();
+        assert_eq!(
+            items.iter().map(|x| x.to_vec()).collect::<Vec<_>>(),
+            vec![vec![1], vec![0], vec![]]
+        );
+
+        assert!(graph.project_bipartite(Side::Left(7), &folder()).is_err());
+    }
+
+    #[test]
+    fn edge_list_file_parallel() {
+        let edges = (0..5000u32)
+            .map(|i| ((i * 7919) % 700, (i * 104729) % 800))
+            .collect::<Vec<_>>();
+        let mut sorted = edges.clone();
+        sorted.sort_unstable();
+
+        let source_file_name = format!("/tmp/tmp_src_{}", rand::random::<u32>());
+        let unsorted_file_name = format!("/tmp/tmp_src_{}", rand::random::<u32>());
+        let to_text = |edges: &[(u32, u32)]| {
+            edges
+                .iter()
+                .map(|(src, dst)| format!("{} {}\n", src, dst))
+                .collect::<String>()
+        };
+        std::fs::write(&source_file_name, to_text(&sorted)).unwrap();
+        std::fs::write(&unsorted_file_name, to_text(&edges)).unwrap();
+
+        let sequential = Graph::<u32>::from_txt_adjacency_list(
+            std::fs::File::open(&source_file_name).unwrap(),
+            &format!("/tmp/tmp_dst_{}", rand::random::<u32>()),
+        )
+        .unwrap();
+
+        for (file_name, num_threads) in [(&source_file_name, 4), (&unsorted_file_name, 3)] {
+            let parallel = Graph::<u32>::from_edge_list_file_parallel(
+                file_name,
+                num_threads,
+                &format!("/tmp/tmp_dst_{}", rand::random::<u32>()),
+            )
+            .unwrap();
+            assert_eq!(parallel.as_csr_slices(), sequential.as_csr_slices());
+        }
+
+        std::fs::write(&source_file_name, "0 1\n1 x\n").unwrap();
+        assert!(Graph::<u32>::from_edge_list_file_parallel(
+            &source_file_name,
+            2,
+            &format!("/tmp/tmp_dst_{}", rand::random::<u32>()),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn check_sorted() {
+        assert!(Graph::<u32>::check_sorted("0 1\n0 2\n1 0\n3 1\n".as_bytes()).is_ok());
+        assert!(Graph::<u32>::check_sorted("".as_bytes()).is_ok());
+
+        match Graph::<u32>::check_sorted("0 1\n2 2\n1 0\n".as_bytes()) {
+            Err(error::GraphError::UnsortedEdge {
+                line,
+                content,
+                previous_source,
+            }) => {
+                assert_eq!(line, 3);
+                assert_eq!(content, "1 0");
+                assert_eq!(previous_source, "2");
+            }
+            other => panic!("expected an unsorted edge, got {:?}", other),
+        }
+
+        assert!(matches!(
+            Graph::<u32>::check_sorted("0 1\n1\n".as_bytes()),
+            Err(error::GraphError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn degree_ranks() {
+        let graph = get_graph(&[(0u32, 1u32), (0, 2), (1, 5), (1, 2), (1, 3), (4, 7)]);
+
+        assert_eq!(graph.node_at_rank(0), 1);
+        assert_eq!(graph.node_at_rank(1), 0);
+        assert_eq!(graph.node_at_rank(2), 4);
+        assert_eq!(graph.node_at_rank(7), 7);
+        assert_eq!(
+            graph.iter_by_degree_desc().take(4).collect::<Vec<_>>(),
+            vec![(1, 3), (0, 2), (4, 1), (2, 0)]
+        );
+        assert_eq!(graph.iter_by_degree_desc().count(), graph.n_nodes());
+    }
 
-#[cfg(test)]
-mod tests {
-    use std::fs;
-    use std::io::{BufWriter, Write};
+    #[test]
+    fn subgraph_edges() {
+        let graph = get_graph(&[(0u32, 1u32), (0, 2), (1, 2), (1, 5), (2, 0), (4, 7)]);
 
-    use super::*;
+        let nodes = HashSet::from([2, 0, 1, 9]);
+        assert_eq!(
+            graph.subgraph_edges(&nodes).collect::<Vec<_>>(),
+            vec![(0, 1), (0, 2), (1, 2), (2, 0)]
+        );
+
+        let nodes = HashSet::from([4, 5]);
+        assert_eq!(graph.subgraph_edges(&nodes).count(), 0);
+    }
 
     #[test]
-    fn parse_from_file() {
-        let edges = vec![(0u32, 1u32), (0, 2), (1, 5), (1, 2), (4, 7)];
+    fn degrees() {
+        let graph = get_graph(&[(0u32, 1u32), (0, 2), (1, 5), (1, 2), (4, 7)]);
+
+        assert_eq!(graph.degree(0), 2);
+        assert_eq!(graph.degree(7), 0);
+        assert_eq!(graph.degree_distribution(), vec![5, 1, 2]);
+        assert_eq!(graph.degree_sequence(), vec![2, 2, 1, 0, 0, 0, 0, 0]);
+        assert_eq!(graph.max_degree(), (0, 2));
+        assert_eq!(graph.min_degree(), (2, 0));
+        assert_eq!(graph.avg_degree(), 5.0 / 8.0);
+        assert_eq!(graph.n_isolated_nodes(), 2);
+        assert_eq!(graph.n_zero_out_degree_nodes(), 5);
+    }
 
-        let expected_nodes = vec![0usize, 2, 4, 4, 4, 5, 5, 5, 5];
-        let expected_edges = vec![1u32, 2, 5, 2, 7];
+    #[test]
+    fn counts_in_range() {
+        let graph = get_graph(&[(0u32, 1u32), (0, 2), (1, 5), (1, 2), (4, 7)]);
+
+        assert_eq!(graph.node_count_in_range(2, 5), 3);
+        assert_eq!(graph.node_count_in_range(5, 100), 3);
+        assert_eq!(graph.node_count_in_range(5, 2), 0);
+        assert_eq!(graph.edge_count_in_node_range(0, 2), 4);
+        assert_eq!(graph.edge_count_in_node_range(1, 5), 3);
+        assert_eq!(graph.edge_count_in_node_range(2, 4), 0);
+        assert_eq!(graph.edge_count_in_node_range(0, 100), 5);
+        assert_eq!(graph.edge_count_in_node_range(6, 1), 0);
+    }
 
-        let source_file_name = format!("/tmp/tmp_src_{}", rand::random::<u32>());
+    #[test]
+    fn stats() {
+        let graph = get_graph(&[(0u32, 1u32), (0, 2), (1, 5), (1, 2), (4, 7)]);
+
+        assert_eq!(
+            graph.stats(),
+            GraphStats {
+                n_nodes: 8,
+                n_edges: 5,
+                max_degree: (0, 2),
+                min_degree: (2, 0),
+                avg_degree: 5.0 / 8.0,
+                density: 5.0 / 56.0,
+            }
+        );
+    }
+
+    #[test]
+    fn density() {
+        let graph = get_graph(&[(0u32, 1u32), (0, 2), (1, 5), (1, 2), (4, 7)]);
+        assert_eq!(graph.density(), 5.0 / 56.0);
+        assert!(!graph.is_sparse_default());
+        assert!(graph.is_sparse(0.1));
+
+        let complete = get_graph(&[(0u32, 1u32), (0, 2), (1, 0), (1, 2), (2, 0), (2, 1)]);
+        assert_eq!(complete.density(), 1.0);
+
+        let single = get_graph(&[(0u32, 0u32)]);
+        assert_eq!(single.density(), 0.0);
+    }
+
+    #[test]
+    fn edges_sorted_per_node() {
+        let destination_folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+        let graph = Graph::from_adjacency_list(
+            [(0u32, 3u32), (0, 1), (0, 2), (1, 0), (2, 1), (2, 0)]
+                .into_iter()
+                .map(Ok),
+            &destination_folder_name,
+        )
+        .unwrap();
+
+        assert_eq!(graph.neighbors(0), &[1, 2, 3]);
+        assert_eq!(graph.neighbors(2), &[0, 1]);
+        assert!(graph.has_sorted_neighbors());
+        assert!(
+            GraphMetadata::load(&destination_folder_name)
+                .unwrap()
+                .unwrap()
+                .edges_sorted_per_node
+        );
+
+        // Arrays given as is keep their order, and the metadata says so
+        let unsorted_folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+        Graph::<u32>::from_csr_arrays(&[0, 2, 2], &[1, 0], &unsorted_folder_name).unwrap();
+        assert!(
+            !GraphMetadata::load(&unsorted_folder_name)
+                .unwrap()
+                .unwrap()
+                .edges_sorted_per_node
+        );
+    }
+
+    #[test]
+    fn metadata_validation() {
+        let edges = [(0u32, 1u32), (0, 2), (1, 5), (1, 2), (4, 7)];
         let destination_folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
 
-        let file = fs::OpenOptions::new()
-            .write(true)
-            .create(true)
-            .open(&source_file_name)
+        Graph::<u32>::from_adjacency_list(edges.iter().map(|x| Ok(*x)), &destination_folder_name)
             .unwrap();
 
-        // Write edges to file
-        let mut writer = BufWriter::new(&file);
-        for edge in edges {
-            let line = format!("{} {}\n", edge.0, edge.1);
-            writer.write(line.as_bytes()).unwrap();
-        }
+        let metadata = GraphMetadata::load(&destination_folder_name)
+            .unwrap()
+            .unwrap();
+        assert_eq!(metadata.n_nodes, 8);
+        assert_eq!(metadata.n_edges, 5);
+        assert_eq!(metadata.node_type_bits, 32);
+
+        // Loading with the wrong node type is detected
+        let error = match Graph::<u64>::load_graph(&destination_folder_name) {
+            Ok(_) => panic!("Loaded a graph with the wrong node type"),
+            Err(e) => e,
+        };
+        assert!(matches!(
+            error
+                .get_ref()
+                .and_then(|e| e.downcast_ref::<error::GraphError>()),
+            Some(error::GraphError::MetadataMismatch { .. })
+        ));
+
+        // Folders without metadata are still supported
+        std::fs::remove_file(format!(
+            "{}/{}",
+            destination_folder_name,
+            metadata::METADATA_NAME
+        ))
+        .unwrap();
+        assert_eq!(
+            Graph::<u32>::load_graph(&destination_folder_name)
+                .unwrap()
+                .n_edges(),
+            5
+        );
+    }
 
-        drop(writer);
+    #[test]
+    fn induced_subgraph() {
+        let graph = get_graph(&[(0u32, 1u32), (0, 2), (1, 5), (1, 2), (4, 7)]);
 
-        let file = fs::OpenOptions::new()
-            .read(true)
-            .open(&source_file_name)
+        let (subgraph, mapping) = graph
+            .induced_subgraph(
+                &[1, 2, 5, 6],
+                &format!("/tmp/tmp_dst_{}", rand::random::<u32>()),
+            )
             .unwrap();
 
-        let graph = match Graph::<u32>::from_txt_adjacency_list(file, &destination_folder_name) {
-            Ok(graph) => graph,
-            Err(e) => panic!("{:?}", e),
-        };
+        assert_eq!(mapping, vec![1, 2, 5, 6]);
+        assert_eq!(
+            subgraph.iter().map(|e| e.to_vec()).collect::<Vec<_>>(),
+            vec![vec![1, 2], vec![], vec![], vec![]]
+        );
 
-        // Check correctness
+        for invalid in [&[2usize, 1][..], &[1, 1], &[3, 8], &[]] {
+            assert!(graph
+                .induced_subgraph(invalid, &format!("/tmp/tmp_dst_{}", rand::random::<u32>()))
+                .is_err());
+        }
+
+        // Subgraphs without any edge are still valid graphs
+        let (subgraph, _) = graph
+            .induced_subgraph(&[3, 6], &format!("/tmp/tmp_dst_{}", rand::random::<u32>()))
+            .unwrap();
+        assert_eq!(subgraph.n_nodes(), 2);
+        assert_eq!(subgraph.n_edges(), 0);
+    }
+
+    #[test]
+    fn union() {
+        let a = get_graph(&[(0u32, 1u32), (0, 2), (1, 2)]);
+        let b = get_graph(&[(0u32, 2u32), (2, 0), (3, 4)]);
+        let c = get_graph(&[(0u32, 0u32), (1, 2), (5, 1)]);
+        let folder = || format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+        let edges = |graph: &Graph<u32>| graph.iter().map(|x| x.to_vec()).collect::<Vec<_>>();
+
+        let ab = a.union(&b, &folder()).unwrap();
         assert_eq!(
-            graph
-                .iterate_nodes()
-                .map(|x| x.clone())
-                .collect::<Vec<usize>>(),
-            expected_nodes
+            edges(&ab),
+            vec![vec![1, 2], vec![2], vec![0], vec![4], vec![]]
         );
+
+        // Commutativity
+        assert_eq!(edges(&b.union(&a, &folder()).unwrap()), edges(&ab));
+
+        // Associativity
+        let ab_c = ab.union(&c, &folder()).unwrap();
+        let a_bc = a
+            .union(&b.union(&c, &folder()).unwrap(), &folder())
+            .unwrap();
+        assert_eq!(edges(&ab_c), edges(&a_bc));
+        assert_eq!(ab_c.n_nodes(), 6);
+        assert_eq!(ab_c.neighbors(0), &[0, 1, 2]);
+    }
+
+    #[test]
+    fn shrink_to_giant_component() {
+        // The components are {0, 1, 2}, {3, 4, 5, 6} with a self loop on 6, and {7}
+        let graph = get_graph(&[(0u32, 1u32), (2, 1), (4, 3), (4, 5), (5, 6), (6, 6), (7, 7)]);
+
+        let (giant, discarded_nodes, discarded_edges) = graph
+            .shrink_to_giant_component(&format!("/tmp/tmp_dst_{}", rand::random::<u32>()))
+            .unwrap();
+
+        assert_eq!(discarded_nodes, 4);
+        assert_eq!(discarded_edges, 3);
         assert_eq!(
-            graph
-                .iterate_edges()
-                .map(|x| x.clone())
-                .collect::<Vec<u32>>(),
-            expected_edges
+            giant.iter().map(|x| x.to_vec()).collect::<Vec<_>>(),
+            vec![vec![], vec![0, 2], vec![3], vec![3]]
         );
     }
 
     #[test]
-    fn parse_from_binary() {
-        let edges = vec![(0u32, 1u32), (0, 2), (1, 5), (1, 2), (4, 7)];
-        let expected_nodes = vec![0usize, 2, 4, 4, 4, 5, 5, 5, 5];
-        let expected_edges = vec![1u32, 2, 5, 2, 7];
+    fn bfs_order() {
+        let graph = get_graph(&[(0u32, 1u32), (0, 2), (1, 5), (1, 2), (4, 7)]);
 
-        let destionation_folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+        assert_eq!(graph.bfs_order(0), vec![0, 1, 2, 5, 3, 4, 7, 6]);
 
-        let edges_flatten = edges
+        let order = graph.bfs_order(4);
+        assert_eq!(order[0], 4);
+        assert_eq!(order[1], 7);
+        assert_eq!(order.len(), graph.n_nodes());
+
+        // The result is a permutation of all nodes
+        let mut sorted = order.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..graph.n_nodes()).collect::<Vec<_>>());
+    }
+
+    /// Adds the reverse of every edge of `graph`, without duplicates.
+    fn to_undirected<'a>(graph: &Graph<u32>) -> Graph<'a, u32> {
+        let mut edges = graph
             .iter()
-            .flat_map(|x| vec![x.0, x.1])
-            .collect::<Vec<u32>>();
+            .enumerate()
+            .flat_map(|(src, edges)| {
+                edges
+                    .iter()
+                    .flat_map(move |dst| [(src as u32, *dst), (*dst, src as u32)])
+            })
+            .collect::<Vec<_>>();
+        edges.sort_unstable();
+        edges.dedup();
+
+        Graph::from_sorted_edges(
+            edges.into_iter().map(Ok),
+            graph.n_nodes(),
+            &format!("/tmp/tmp_dst_{}", rand::random::<u32>()),
+        )
+        .unwrap()
+    }
 
-        // Transform into slice of u8 for Read
-        let edges_flatten = unsafe {
-            std::slice::from_raw_parts(
-                edges_flatten.as_ptr() as *const u8,
-                edges_flatten.len() * std::mem::size_of::<u32>(),
-            )
+    #[test]
+    fn partition() {
+        // Two cliques of 4 nodes, joined by a single edge
+        let clique = |offset: u32| {
+            (0..4).flat_map(move |a| {
+                (0..4)
+                    .filter(move |b| a != *b)
+                    .map(move |b| (a + offset, b + offset))
+            })
         };
+        let mut edges = clique(0)
+            .chain(clique(4))
+            .chain([(3, 4)])
+            .collect::<Vec<_>>();
+        edges.sort_unstable();
+        let graph = to_undirected(&get_graph(&edges));
+
+        // The greedy assignment depends on the starting node, so it is the same for the same seed
+        let parts = graph.partition(2, &mut StdRng::seed_from_u64(42));
+        assert_eq!(parts, vec![1, 1, 1, 0, 0, 0, 0, 1]);
+        assert_eq!(parts, graph.partition(2, &mut StdRng::seed_from_u64(42)));
+        assert_eq!(graph.partition_quality(&parts), 12.0 / 26.0);
 
-        let graph =
-            match Graph::<u32>::from_binary_adjancency(edges_flatten, &destionation_folder_name) {
-                Ok(graph) => graph,
-                Err(e) => panic!("{:?}", e),
-            };
-
-        // Check correctness
         assert_eq!(
-            graph
-                .iterate_nodes()
-                .map(|x| x.clone())
-                .collect::<Vec<usize>>(),
-            expected_nodes
+            graph.partition_quality(&[0, 0, 0, 0, 1, 1, 1, 1]),
+            2.0 / 26.0
         );
-
         assert_eq!(
-            graph
-                .iterate_edges()
-                .map(|x| x.clone())
-                .collect::<Vec<u32>>(),
-            expected_edges
+            graph.partition(1, &mut StdRng::seed_from_u64(42)),
+            vec![0; 8]
         );
+        assert_eq!(graph.partition_quality(&[0, 1, 2, 3, 4, 5, 6, 7]), 1.0);
     }
 
     #[test]
-    fn parse_from_general_stream() {
-        let edges = vec![(0u32, 1u32), (0, 2), (1, 5), (1, 2), (4, 7)];
-
-        let expected_nodes = vec![0usize, 2, 4, 4, 4, 5, 5, 5, 5];
-        let expected_edges = vec![1u32, 2, 5, 2, 7];
+    fn partition_without_nodes() {
+        // A vertex file holding only the first offset describes a graph without nodes
+        let folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+        fs::create_dir(&folder_name).unwrap();
+        fs::write(
+            format!("{}/{}", folder_name, reading::VERTEX_NAME),
+            0usize.to_ne_bytes(),
+        )
+        .unwrap();
+        fs::write(format!("{}/{}", folder_name, reading::EDGE_NAME), []).unwrap();
+
+        let graph = Graph::<u32>::load_graph(&folder_name).unwrap();
+        assert_eq!(graph.n_nodes(), 0);
+        assert!(graph
+            .partition(2, &mut StdRng::seed_from_u64(42))
+            .is_empty());
+    }
 
+    #[test]
+    fn k_hop_neighborhood() {
+        let edges = vec![(0u32, 1u32), (0, 3), (1, 2), (2, 0), (3, 4), (4, 5)];
         let destination_folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+        let graph = Graph::from_adjacency_list(edges.into_iter().map(Ok), &destination_folder_name)
+            .unwrap();
 
-        // Read from string bytes stream
-        let graph = match Graph::<u32>::from_adjacency_list(
-            edges.iter().map(|x| Ok(x.clone())),
-            &destination_folder_name,
-        ) {
-            Ok(graph) => graph,
-            Err(e) => panic!("{:?}", e),
-        };
-
-        println!("Destionation folder: {}", destination_folder_name);
-
+        assert_eq!(graph.k_hop_neighborhood(0, 0, true), vec![0]);
+        assert_eq!(graph.k_hop_neighborhood(0, 0, false), Vec::<usize>::new());
+        assert_eq!(graph.k_hop_neighborhood(0, 1, true), vec![0, 1, 3]);
+        assert_eq!(graph.k_hop_neighborhood(0, 2, false), vec![1, 2, 3, 4]);
+        // Going back to the source does not add it
+        assert_eq!(graph.k_hop_neighborhood(1, 2, false), vec![0, 2]);
         assert_eq!(
-            graph
-                .iterate_nodes()
-                .map(|x| x.clone())
-                .collect::<Vec<usize>>(),
-            expected_nodes
+            graph.k_hop_neighborhood(0, 10, true),
+            vec![0, 1, 2, 3, 4, 5]
         );
+        assert_eq!(graph.k_hop_neighborhood(5, 3, true), vec![5]);
+    }
+
+    #[test]
+    fn sorted_by_degree() {
+        let graph = get_graph(&[(0u32, 1u32), (1, 0), (1, 2), (1, 3), (3, 2), (3, 0)]);
+
+        let sorted = graph
+            .sorted_by_degree(&format!("/tmp/tmp_dst_{}", rand::random::<u32>()))
+            .unwrap();
+
+        // Node 1 becomes 0, node 3 becomes 1, node 0 becomes 2 and node 2 becomes 3
+        assert_eq!(sorted.degree_sequence(), vec![3, 2, 1, 0]);
         assert_eq!(
-            graph
-                .iterate_edges()
-                .map(|x| x.clone())
-                .collect::<Vec<u32>>(),
-            expected_edges
+            sorted.iter().map(|x| x.to_vec()).collect::<Vec<_>>(),
+            vec![vec![1, 2, 3], vec![2, 3], vec![0], vec![]]
         );
     }
 
     #[test]
-    fn load_u64_graph() {
-        let edges = vec![(0u64, 1u64), (0, 2), (1, 5), (1, 2), (4, 7)];
+    fn transpose() {
+        let graph = get_graph(&[(0u32, 1u32), (0, 2), (1, 5), (1, 2), (4, 7)]);
 
-        let expected_nodes = vec![0usize, 2, 4, 4, 4, 5, 5, 5, 5];
-        let expected_edges = vec![1u64, 2, 5, 2, 7];
+        let transposed = graph
+            .transpose(&format!("/tmp/tmp_dst_{}", rand::random::<u32>()))
+            .unwrap();
 
-        let destination_folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+        assert_eq!(
+            transposed.iter().map(|x| x.to_vec()).collect::<Vec<_>>(),
+            vec![
+                vec![],
+                vec![0],
+                vec![0, 1],
+                vec![],
+                vec![],
+                vec![1],
+                vec![],
+                vec![4]
+            ]
+        );
+    }
 
-        let graph = match Graph::<u64>::from_adjacency_list(
-            edges.iter().map(|x| Ok(x.clone())),
-            &destination_folder_name,
-        ) {
-            Ok(graph) => graph,
-            Err(e) => panic!("{:?}", e),
-        };
+    #[test]
+    fn reorder() {
+        let graph = get_graph(&[(0u32, 1u32), (0, 2), (1, 5), (1, 2), (4, 7)]);
+        let permutation = [3, 0, 7, 1, 6, 2, 4, 5];
+
+        let reordered = graph
+            .reorder(
+                &permutation,
+                &format!("/tmp/tmp_dst_{}", rand::random::<u32>()),
+            )
+            .unwrap();
+        assert_eq!(
+            reordered.iter().map(|e| e.to_vec()).collect::<Vec<_>>(),
+            vec![
+                vec![2, 7],
+                vec![],
+                vec![],
+                vec![0, 7],
+                vec![],
+                vec![],
+                vec![5],
+                vec![]
+            ]
+        );
 
+        // Reordering commutes with making the graph undirected
+        let first = to_undirected(&graph)
+            .reorder(
+                &permutation,
+                &format!("/tmp/tmp_dst_{}", rand::random::<u32>()),
+            )
+            .unwrap();
+        let second = to_undirected(&reordered);
         assert_eq!(
-            graph
-                .iterate_nodes()
-                .map(|x| x.clone())
-                .collect::<Vec<usize>>(),
-            expected_nodes
+            first.iter().map(|e| e.to_vec()).collect::<Vec<_>>(),
+            second.iter().map(|e| e.to_vec()).collect::<Vec<_>>()
         );
 
+        for invalid in [
+            &[0usize, 1][..],
+            &[0, 0, 1, 2, 3, 4, 5, 6],
+            &[8, 0, 1, 2, 3, 4, 5, 6],
+        ] {
+            assert!(graph
+                .reorder(invalid, &format!("/tmp/tmp_dst_{}", rand::random::<u32>()))
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn clone_to() {
+        let graph = get_graph(&[(0u32, 1u32), (0, 2), (1, 5), (1, 2), (4, 7)]);
+        let folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+
+        let clone = graph.clone_to(&folder_name).unwrap();
         assert_eq!(
-            graph
-                .iterate_edges()
-                .map(|x| x.clone())
-                .collect::<Vec<u64>>(),
-            expected_edges
+            clone.iter().map(|e| e.to_vec()).collect::<Vec<_>>(),
+            graph.iter().map(|e| e.to_vec()).collect::<Vec<_>>()
         );
+        assert_eq!(Graph::<u32>::load_graph(&folder_name).unwrap().n_edges(), 5);
+
+        // The copy has its own metadata
+        let metadata = GraphMetadata::load(&folder_name).unwrap().unwrap();
+        assert_eq!(metadata.n_nodes, 8);
+        assert_eq!(metadata.n_edges, 5);
+        assert!(metadata.edges_sorted_per_node);
+        assert!(metadata.validate::<u32>(8, 5).is_ok());
+
+        // The destination is not overwritten
+        assert!(graph.clone_to(&folder_name).is_err());
+
+        let in_memory = Graph::<u32>::from_csr_vecs(vec![0, 1, 1], vec![1]).unwrap();
+        let clone = in_memory
+            .clone_to(&format!("/tmp/tmp_dst_{}", rand::random::<u32>()))
+            .unwrap();
+        assert_eq!(clone.neighbors(0), &[1]);
     }
 
     #[test]
-    fn test_graph_load() {
-        let edges = vec![(0u32, 1u32), (0, 2), (1, 5), (1, 2), (4, 7)];
-        let expected_nodes = vec![0usize, 2, 4, 4, 4, 5, 5, 5, 5];
-        let expected_edges = vec![1u32, 2, 5, 2, 7];
+    fn construction_progress() {
+        let edges = (0..25u32).map(|i| (i, i + 1)).collect::<Vec<_>>();
+        let mut reported = Vec::new();
+
+        let graph = Graph::<u32>::from_adjacency_list_with_progress(
+            edges.into_iter().map(Ok),
+            &format!("/tmp/tmp_dst_{}", rand::random::<u32>()),
+            Some(10),
+            Some(|processed| reported.push(processed)),
+        )
+        .unwrap();
 
-        let destination_folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+        assert_eq!(graph.n_edges(), 25);
+        assert_eq!(reported, vec![10, 20, 25]);
 
-        match Graph::<u32>::from_adjacency_list(
-            edges.iter().map(|x| Ok(x.clone())),
-            &destination_folder_name,
-        ) {
-            Ok(_) => {}
-            Err(e) => panic!("{:?}", e),
+        assert!(Graph::<u32>::from_adjacency_list_with_progress(
+            [(0u32, 1u32)].into_iter().map(Ok),
+            &format!("/tmp/tmp_dst_{}", rand::random::<u32>()),
+            None,
+            None::<fn(u64)>,
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn construction_progress_exact_multiple() {
+        let edges = (0..20u32).map(|i| (i, i + 1)).collect::<Vec<_>>();
+        let mut reported = Vec::new();
+
+        Graph::<u32>::from_adjacency_list_with_progress(
+            edges.into_iter().map(Ok),
+            &format!("/tmp/tmp_dst_{}", rand::random::<u32>()),
+            Some(10),
+            Some(|processed| reported.push(processed)),
+        )
+        .unwrap();
+
+        assert_eq!(reported, vec![10, 20]);
+    }
+
+    #[test]
+    fn assert_invariants() {
+        get_graph(&[(0u32, 1u32), (0, 2), (1, 5), (1, 2), (4, 7)]).assert_invariants();
+        Graph::<u32>::from_csr_vecs(vec![0, 1, 1], vec![1])
+            .unwrap()
+            .assert_invariants();
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "the offsets decrease at node 1: 2 > 1")]
+    fn assert_invariants_decreasing_offsets() {
+        let graph = Graph::<u32> {
+            nodes: Storage::Memory(vec![0, 2, 1, 2]),
+            edges: Storage::Memory(vec![1, 2]),
         };
+        graph.assert_invariants();
+    }
 
-        // Load graph from memory
-        let graph = match Graph::<u32>::load_graph(&destination_folder_name) {
-            Ok(graph) => graph,
-            Err(e) => panic!("{:?}", e),
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "edge 1 points to node 3, but there are only 2 nodes")]
+    fn assert_invariants_missing_node() {
+        let graph = Graph::<u32> {
+            nodes: Storage::Memory(vec![0, 1, 2]),
+            edges: Storage::Memory(vec![1, 3]),
         };
+        graph.assert_invariants();
+    }
+
+    #[test]
+    fn verify_integrity() {
+        let edges = [(0u32, 1u32), (0, 2), (1, 5), (1, 2), (4, 7)];
+        let destination_folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+
+        let graph = Graph::<u32>::from_adjacency_list(
+            edges.iter().map(|x| Ok(*x)),
+            &destination_folder_name,
+        )
+        .unwrap();
+        assert!(graph.verify_integrity().is_ok());
+
+        // Truncate the edge file, without updating the metadata
+        std::fs::remove_file(format!(
+            "{}/{}",
+            destination_folder_name,
+            metadata::METADATA_NAME
+        ))
+        .unwrap();
+        let edges_file = reading::get_edge_file(&destination_folder_name).unwrap();
+        edges_file.set_len(4 * 4).unwrap();
+
+        let corrupted = Graph::<u32>::load_from_files(
+            reading::get_vertex_file(&destination_folder_name).unwrap(),
+            edges_file,
+        )
+        .unwrap();
+        assert!(matches!(
+            corrupted.verify_integrity(),
+            Err(error::GraphError::CorruptedCsr(_))
+        ));
+
+        #[cfg(debug_assertions)]
+        assert!(Graph::<u32>::load_graph(&destination_folder_name).is_err());
+    }
+
+    #[test]
+    fn node_attributes() {
+        let destination_folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+        let graph = Graph::from_adjacency_list(
+            [(0u32, 1u32), (1, 2)].into_iter().map(Ok),
+            &destination_folder_name,
+        )
+        .unwrap();
+
+        assert!(graph
+            .save_attributes(&[1.5f64, 2.5], &destination_folder_name)
+            .is_err());
+        graph
+            .save_attributes(&[1.5f64, 2.5, -1.0], &destination_folder_name)
+            .unwrap();
+
+        let (loaded, attributes) =
+            Graph::<u32>::load_with_attributes::<f64>(&destination_folder_name).unwrap();
+        assert_eq!(loaded.n_nodes(), 3);
+        assert_eq!(attributes, vec![1.5, 2.5, -1.0]);
 
         assert_eq!(
-            graph
-                .iterate_nodes()
-                .map(|x| x.clone())
-                .collect::<Vec<usize>>(),
-            expected_nodes
+            Graph::<u32>::load_with_attributes::<u32>(&destination_folder_name)
+                .err()
+                .unwrap()
+                .kind(),
+            std::io::ErrorKind::InvalidData
         );
+    }
+
+    #[test]
+    fn missing_file_names_path() {
+        let folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+
+        let error = Graph::<u32>::load_graph(&folder_name).err().unwrap();
+        assert_eq!(error.kind(), std::io::ErrorKind::NotFound);
+        assert!(error
+            .to_string()
+            .contains(&format!("{}/{}", folder_name, reading::VERTEX_NAME)));
+        assert!(matches!(
+            error.get_ref().unwrap().downcast_ref::<error::GraphError>(),
+            Some(error::GraphError::FileAccess { .. })
+        ));
+    }
+
+    #[test]
+    fn load_readonly() {
+        let destination_folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+        let graph = Graph::from_adjacency_list(
+            [(0u32, 1u32), (0, 2), (1, 5), (1, 2), (4, 7)]
+                .into_iter()
+                .map(Ok),
+            &destination_folder_name,
+        )
+        .unwrap();
+
+        for name in [reading::VERTEX_NAME, reading::EDGE_NAME] {
+            let path = format!("{}/{}", destination_folder_name, name);
+            let mut permissions = fs::metadata(&path).unwrap().permissions();
+            permissions.set_readonly(true);
+            fs::set_permissions(&path, permissions).unwrap();
+        }
+
+        let loaded = Graph::<u32>::load_graph_readonly(&destination_folder_name).unwrap();
+        assert_eq!(loaded.as_csr_slices(), graph.as_csr_slices());
 
+        // Missing files are not created
+        let empty_folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+        fs::create_dir(&empty_folder_name).unwrap();
         assert_eq!(
-            graph
-                .iterate_edges()
-                .map(|x| x.clone())
-                .collect::<Vec<u32>>(),
-            expected_edges
+            Graph::<u32>::load_graph(&empty_folder_name)
+                .err()
+                .unwrap()
+                .kind(),
+            std::io::ErrorKind::NotFound
         );
+        assert_eq!(fs::read_dir(&empty_folder_name).unwrap().count(), 0);
+        let _ = Graph::<u32>::load_graph_readwrite(&empty_folder_name);
+        assert_eq!(fs::read_dir(&empty_folder_name).unwrap().count(), 2);
     }
 
     #[test]
     fn iterate_graph() {
-        let edges = vec![(0u32, 1u32), (0, 2), (1, 5), (1, 2), (4, 7)];
+        let edges = [(0u32, 1u32), (0, 2), (1, 5), (1, 2), (4, 7)];
         let expected_res = vec![
             (0usize, vec![1, 2]),
-            (1, vec![5, 2]),
+            (1, vec![2, 5]),
             (2, vec![]),
             (3, vec![]),
             (4, vec![7]),
@@ -443,7 +3119,7 @@ mod tests {
         let destination_folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
 
         let graph = match Graph::<u32>::from_adjacency_list(
-            edges.iter().map(|x| Ok(x.clone())),
+            edges.iter().map(|x| Ok(*x)),
             &destination_folder_name,
         ) {
             Ok(g) => g,
@@ -460,6 +3136,52 @@ mod tests {
         );
     }
 
+    #[test]
+    fn iterate_edges_flat() {
+        let edges = vec![(0u32, 1u32), (0, 2), (1, 2), (1, 5), (4, 7)];
+        let graph = get_graph(&edges);
+
+        let expected = edges
+            .iter()
+            .map(|(src, dst)| (*src as usize, *dst))
+            .collect::<Vec<_>>();
+        assert_eq!(graph.edge_iter().collect::<Vec<_>>(), expected);
+
+        let mut parallel = graph.par_edge_iter().collect::<Vec<_>>();
+        parallel.sort_unstable();
+        let mut expected = expected;
+        expected.sort_unstable();
+        assert_eq!(parallel, expected);
+    }
+
+    #[test]
+    fn iterate_from_node() {
+        let graph = get_graph(&[(0u32, 1u32), (0, 2), (1, 5), (1, 2), (4, 7)]);
+
+        let mut iter = graph.iter();
+        iter.skip_to(4);
+        assert_eq!(iter.next(), Some(&[7u32][..]));
+        assert_eq!(iter.count(), 3);
+
+        // Moving backwards works too, and a thread can process a range of nodes
+        let mut iter = graph.iter();
+        iter.skip_to(6).skip_to(1);
+        assert_eq!(
+            iter.take(2).map(|x| x.to_vec()).collect::<Vec<_>>(),
+            vec![vec![2, 5], vec![]]
+        );
+
+        let mut iter = graph.iter();
+        assert_eq!(iter.skip_to(graph.n_nodes()).next(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn iterate_from_invalid_node() {
+        let graph = get_graph(&[(0u32, 1u32)]);
+        graph.iter().skip_to(3);
+    }
+
     #[test]
     fn invalid() {}
 }
@@ -0,0 +1,187 @@
+use std::{
+    fs,
+    io::{ErrorKind, Result},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use super::error::GraphError;
+
+/// The name of the metadata sidecar inside a graph folder.
+pub const METADATA_NAME: &str = "meta.json";
+
+/// Describes the graph files stored in a folder, so that they can be validated when loaded.
+/// It is stored as a flat JSON object in [METADATA_NAME], next to the vertex and edge files.
+/// With the `serde` feature, it can also be serialized into any other format.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GraphMetadata {
+    /// The number of nodes in the graph.
+    pub n_nodes: usize,
+    /// The number of edges in the graph.
+    pub n_edges: usize,
+    /// The width in bits of the node type `N` used in the edge file.
+    pub node_type_bits: usize,
+    /// When the graph was constructed, in seconds since the Unix epoch.
+    pub created_at: u64,
+    /// Whether the edges of every node are sorted by destination, which allows binary searches and sorted intersections.
+    /// Metadata written before this flag existed does not have it, and is read as `false`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub edges_sorted_per_node: bool,
+}
+
+impl GraphMetadata {
+    /// Creates the metadata of a graph with node type `N` constructed right now.
+    pub fn new<N>(n_nodes: usize, n_edges: usize) -> Self {
+        GraphMetadata {
+            n_nodes,
+            n_edges,
+            node_type_bits: std::mem::size_of::<N>() * 8,
+            created_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            edges_sorted_per_node: false,
+        }
+    }
+
+    /// Sets whether the edges of every node are sorted by destination.
+    pub fn with_edges_sorted_per_node(mut self, sorted: bool) -> Self {
+        self.edges_sorted_per_node = sorted;
+        self
+    }
+
+    /// Serializes the metadata as a JSON object.
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\n  \"n_nodes\": {},\n  \"n_edges\": {},\n  \"node_type_bits\": {},\n  \"created_at\": {},\n  \"edges_sorted_per_node\": {}\n}}\n",
+            self.n_nodes, self.n_edges, self.node_type_bits, self.created_at, self.edges_sorted_per_node
+        )
+    }
+
+    /// Parses the metadata from a flat JSON object. Unknown fields are ignored.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let body = json
+            .trim()
+            .strip_prefix('{')
+            .and_then(|body| body.strip_suffix('}'))
+            .ok_or(ErrorKind::InvalidData)?;
+
+        let mut n_nodes = None;
+        let mut n_edges = None;
+        let mut node_type_bits = None;
+        let mut created_at = None;
+        let mut edges_sorted_per_node = false;
+
+        for field in body.split(',').filter(|field| !field.trim().is_empty()) {
+            let (key, value) = field.split_once(':').ok_or(ErrorKind::InvalidData)?;
+            let value = value.trim();
+
+            match key.trim().trim_matches('"') {
+                "n_nodes" => n_nodes = value.parse().ok(),
+                "n_edges" => n_edges = value.parse().ok(),
+                "node_type_bits" => node_type_bits = value.parse().ok(),
+                "created_at" => created_at = value.parse().ok(),
+                "edges_sorted_per_node" => {
+                    edges_sorted_per_node = value.parse().map_err(|_| ErrorKind::InvalidData)?
+                }
+                _ => {}
+            }
+        }
+
+        Ok(GraphMetadata {
+            n_nodes: n_nodes.ok_or(ErrorKind::InvalidData)?,
+            n_edges: n_edges.ok_or(ErrorKind::InvalidData)?,
+            node_type_bits: node_type_bits.ok_or(ErrorKind::InvalidData)?,
+            created_at: created_at.ok_or(ErrorKind::InvalidData)?,
+            edges_sorted_per_node,
+        })
+    }
+
+    /// Writes the metadata sidecar into `folder_name`.
+    pub fn save(&self, folder_name: &str) -> Result<()> {
+        fs::write(format!("{}/{}", folder_name, METADATA_NAME), self.to_json())
+    }
+
+    /// Reads the metadata sidecar from `folder_name`, or returns `None` if the folder does not have one.
+    pub fn load(folder_name: &str) -> Result<Option<Self>> {
+        match fs::read_to_string(format!("{}/{}", folder_name, METADATA_NAME)) {
+            Ok(json) => Self::from_json(&json).map(Some),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Checks that the metadata describes a graph with `n_nodes`, `n_edges` and node type `N`.
+    pub fn validate<N>(
+        &self,
+        n_nodes: usize,
+        n_edges: usize,
+    ) -> std::result::Result<(), GraphError> {
+        let checks = [
+            ("n_nodes", self.n_nodes, n_nodes),
+            ("n_edges", self.n_edges, n_edges),
+            (
+                "node_type_bits",
+                self.node_type_bits,
+                std::mem::size_of::<N>() * 8,
+            ),
+        ];
+
+        for (field, expected, found) in checks {
+            if expected != found {
+                return Err(GraphError::MetadataMismatch {
+                    field,
+                    expected: expected.to_string(),
+                    found: found.to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_round_trip() {
+        let metadata = GraphMetadata::new::<u64>(8, 5);
+        assert_eq!(metadata.node_type_bits, 64);
+        assert_eq!(
+            GraphMetadata::from_json(&metadata.to_json()).unwrap(),
+            metadata
+        );
+
+        assert!(GraphMetadata::from_json("{\"n_nodes\": 8}").is_err());
+
+        let sorted = metadata.clone().with_edges_sorted_per_node(true);
+        assert_eq!(GraphMetadata::from_json(&sorted.to_json()).unwrap(), sorted);
+
+        // Metadata written without the flag does not promise sorted edges
+        let old = "{\"n_nodes\": 8, \"n_edges\": 5, \"node_type_bits\": 64, \"created_at\": 0}";
+        assert!(!GraphMetadata::from_json(old).unwrap().edges_sorted_per_node);
+        assert!(metadata.validate::<u64>(8, 5).is_ok());
+        assert!(matches!(
+            metadata.validate::<u32>(8, 5),
+            Err(GraphError::MetadataMismatch {
+                field: "node_type_bits",
+                ..
+            })
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let metadata = GraphMetadata::new::<u32>(8, 5);
+        let json = serde_json::to_string(&metadata).unwrap();
+
+        assert_eq!(GraphMetadata::from_json(&json).unwrap(), metadata);
+        assert_eq!(
+            serde_json::from_str::<GraphMetadata>(&metadata.to_json()).unwrap(),
+            metadata
+        );
+    }
+}
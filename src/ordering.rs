@@ -0,0 +1,136 @@
+use std::collections::VecDeque;
+
+use super::{util::ValidGraphType, Graph};
+
+/// Computes the Reverse Cuthill-McKee ordering of the graph, which reduces its bandwidth, i.e. how far apart the endpoints of its edges are.
+/// The result is a permutation where `result[old_id] = new_id`, ready to be applied with [Graph::reorder].
+/// The graph is treated as undirected, so both directions of each edge should be stored.
+pub fn rcm<N>(graph: &Graph<N>) -> Vec<usize>
+where
+    N: ValidGraphType,
+{
+    let n_nodes = graph.n_nodes();
+    let mut placed = vec![false; n_nodes];
+    let mut order = Vec::with_capacity(n_nodes);
+
+    // Each component is ordered on its own, starting from its lowest degree node
+    let mut by_degree = (0..n_nodes).collect::<Vec<_>>();
+    by_degree.sort_by_key(|node| graph.degree(*node));
+
+    for start in by_degree {
+        if placed[start] {
+            continue;
+        }
+
+        // Look for a node far away from the start, which makes for narrower levels
+        let peripheral = farthest_node(graph, farthest_node(graph, start, &placed), &placed);
+
+        // Cuthill-McKee: a breadth-first search that visits the neighbors of each node by increasing degree
+        let mut queue = VecDeque::from([peripheral]);
+        placed[peripheral] = true;
+
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+
+            let mut neighbors = graph
+                .neighbors(node)
+                .iter()
+                .map(|x| x.as_())
+                .filter(|x| !placed[*x])
+                .collect::<Vec<_>>();
+            neighbors.sort_unstable_by_key(|x| (graph.degree(*x), *x));
+            neighbors.dedup();
+
+            for neighbor in neighbors {
+                placed[neighbor] = true;
+                queue.push_back(neighbor);
+            }
+        }
+    }
+
+    // Reverse the order, and turn it into a permutation
+    let mut permutation = vec![0; n_nodes];
+    for (i, node) in order.iter().rev().enumerate() {
+        permutation[*node] = i;
+    }
+
+    permutation
+}
+
+/// Runs a breadth-first search from `root` over the nodes that are not `placed` yet,
+/// and returns the lowest degree node of the last level.
+fn farthest_node<N>(graph: &Graph<N>, root: usize, placed: &[bool]) -> usize
+where
+    N: ValidGraphType,
+{
+    let mut visited = placed.to_vec();
+    visited[root] = true;
+    let mut level = vec![root];
+
+    loop {
+        let mut next = Vec::new();
+        for node in level.iter() {
+            for neighbor in graph.neighbors(*node).iter().map(|x| x.as_()) {
+                if !visited[neighbor] {
+                    visited[neighbor] = true;
+                    next.push(neighbor);
+                }
+            }
+        }
+
+        if next.is_empty() {
+            return level
+                .into_iter()
+                .min_by_key(|node| graph.degree(*node))
+                .unwrap_or(root);
+        }
+        level = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The largest distance between the endpoints of an edge.
+    fn bandwidth(graph: &Graph<u32>) -> usize {
+        graph
+            .iter()
+            .enumerate()
+            .flat_map(|(src, edges)| edges.iter().map(move |dst| src.abs_diff(*dst as usize)))
+            .max()
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn rcm_path() {
+        // The path 0 - 3 - 6 - 1 - 4 - 7 - 2 - 5, and the edge 8 - 9
+        let path = [0u32, 3, 6, 1, 4, 7, 2, 5];
+        let mut edges = path
+            .windows(2)
+            .flat_map(|w| [(w[0], w[1]), (w[1], w[0])])
+            .collect::<Vec<_>>();
+        edges.extend([(8, 9), (9, 8)]);
+        edges.sort_unstable();
+
+        let graph = Graph::from_adjacency_list(
+            edges.into_iter().map(Ok),
+            &format!("/tmp/tmp_dst_{}", rand::random::<u32>()),
+        )
+        .unwrap();
+        assert_eq!(bandwidth(&graph), 5);
+
+        let permutation = rcm(&graph);
+        let mut sorted = permutation.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..graph.n_nodes()).collect::<Vec<_>>());
+
+        let reordered = graph
+            .reorder(
+                &permutation,
+                &format!("/tmp/tmp_dst_{}", rand::random::<u32>()),
+            )
+            .unwrap();
+        assert_eq!(bandwidth(&reordered), 1);
+    }
+}
@@ -1,37 +1,75 @@
 use std::{
     fs,
-    io::{BufReader, BufWriter, Read, Result, Write},
+    io::{BufRead, BufReader, BufWriter, Read, Result, Write},
     marker::PhantomData,
 };
 
-use super::util;
+use byteorder::{NativeEndian, ReadBytesExt};
+use rayon::prelude::*;
 
-const VERTEX_NAME: &'static str = "vertex.csr";
-const EDGE_NAME: &'static str = "edge.csr";
+use super::{error, metadata::GraphMetadata, util, ConstructionHint};
+
+/// The default name of the vertex file inside a graph folder.
+pub const VERTEX_NAME: &str = "vertex.csr";
+/// The default name of the edge file inside a graph folder.
+pub const EDGE_NAME: &str = "edge.csr";
+
+/// How many edges are processed between two calls to the progress callback, unless told otherwise.
+pub const DEFAULT_PROGRESS_INTERVAL: u64 = 10_000_000;
+
+/// The sentinel that starts every record of a binary DIMACS file, matching the `a` of an arc line in the text format.
+pub const DIMACS_SENTINEL: u32 = b'a' as u32;
 
 /// A graph's metadata
+#[allow(dead_code)]
 pub struct GraphFiles(pub fs::File, pub fs::File, pub usize, pub usize);
 
-/// Convenience function to create a new vertex file in the `folder_name` directory.
-pub fn get_vertex_file(folder_name: &str) -> Result<fs::File> {
+/// Convenience function to create a new file named `file_name` in the `folder_name` directory.
+/// Fails with a [GraphError::FileAccess](error::GraphError::FileAccess) as the inner error, which names the path.
+pub fn get_file(folder_name: &str, file_name: &str) -> Result<fs::File> {
+    let path = format!("{}/{}", folder_name, file_name);
+
     fs::OpenOptions::new()
         .read(true)
         .write(true)
         .create(true)
-        .open(format!("{}/{}", folder_name, VERTEX_NAME))
+        .truncate(false)
+        .open(&path)
+        .map_err(|source| error::GraphError::FileAccess { path, source }.into())
+}
+
+/// Opens the existing file named `file_name` in the `folder_name` directory for reading only.
+/// Fails with a [GraphError::FileAccess](error::GraphError::FileAccess) as the inner error, which names the path.
+pub fn open_file_readonly(folder_name: &str, file_name: &str) -> Result<fs::File> {
+    let path = format!("{}/{}", folder_name, file_name);
+
+    fs::File::open(&path).map_err(|source| error::GraphError::FileAccess { path, source }.into())
+}
+
+/// Convenience function to create a new vertex file in the `folder_name` directory.
+pub fn get_vertex_file(folder_name: &str) -> Result<fs::File> {
+    get_file(folder_name, VERTEX_NAME)
 }
 
 /// Convenience function to create a new edge file in the `folder_name` directory.
 pub fn get_edge_file(folder_name: &str) -> Result<fs::File> {
-    fs::OpenOptions::new()
-        .read(true)
-        .write(true)
-        .create(true)
-        .open(format!("{}/{}", folder_name, EDGE_NAME))
+    get_file(folder_name, EDGE_NAME)
+}
+
+/// Creates the `folder_name` directory if it does not exist yet.
+pub fn create_folder(folder_name: &str) -> Result<()> {
+    match fs::create_dir(folder_name) {
+        Ok(_) => Ok(()),
+        Err(e) => match e.kind() {
+            std::io::ErrorKind::AlreadyExists => Ok(()),
+            _ => Err(e),
+        },
+    }
 }
 
 /// General function that describes the behaviour of the graph.
 /// Must receive an iterator that yields `std::io::Result<(N,N)>`.
+/// The edges of each node are stored sorted by destination, which is recorded in the metadata sidecar.
 pub fn from_adjacency_list<N, T>(
     stream: T,
     destination_folder_name: &str,
@@ -40,27 +78,157 @@ where
     T: Iterator<Item = std::io::Result<(N, N)>> + Sized,
     N: util::ValidGraphType,
 {
-    // Create directory if does not exist
-    match fs::create_dir(destination_folder_name) {
-        Ok(_) => {},
-        Err(e) => match e.kind() {
-            std::io::ErrorKind::AlreadyExists => {},
-            _ => return Err(e),
-        },
+    from_adjacency_list_with_hint(
+        stream,
+        destination_folder_name,
+        ConstructionHint::CheckSorted,
+    )
+}
+
+/// Same as [from_adjacency_list], except the sort check is only performed if `hint` asks for it.
+/// The metadata sidecar describing the graph is written alongside the vertex and edge files.
+pub fn from_adjacency_list_with_hint<N, T>(
+    stream: T,
+    destination_folder_name: &str,
+    hint: ConstructionHint,
+) -> std::io::Result<GraphFiles>
+where
+    T: Iterator<Item = std::io::Result<(N, N)>> + Sized,
+    N: util::ValidGraphType,
+{
+    let files = write_adjacency_list(
+        stream,
+        destination_folder_name,
+        VERTEX_NAME,
+        EDGE_NAME,
+        hint,
+        0,
+        true,
+    )?;
+
+    GraphMetadata::new::<N>(files.2 - 1, files.3)
+        .with_edges_sorted_per_node(true)
+        .save(destination_folder_name)?;
+
+    Ok(files)
+}
+
+/// Same as [from_adjacency_list], except `on_progress` is called with the number of edges processed so far
+/// every `interval` edges, and once more when all edges are processed, unless that total was just reported.
+// `is_multiple_of` is only available since Rust 1.87, which would raise the minimum supported version
+#[allow(clippy::manual_is_multiple_of)]
+pub fn from_adjacency_list_with_progress<N, T, F>(
+    stream: T,
+    destination_folder_name: &str,
+    interval: u64,
+    mut on_progress: F,
+) -> std::io::Result<GraphFiles>
+where
+    T: Iterator<Item = std::io::Result<(N, N)>> + Sized,
+    N: util::ValidGraphType,
+    F: FnMut(u64),
+{
+    let interval = interval.max(1);
+    let mut processed = 0u64;
+
+    let stream = stream.inspect(|edge| {
+        if edge.is_ok() {
+            processed += 1;
+            if processed % interval == 0 {
+                on_progress(processed);
+            }
+        }
+    });
+    let files = from_adjacency_list(stream, destination_folder_name)?;
+
+    // The last edge may already have been reported by the interval
+    if files.3 as u64 % interval != 0 {
+        on_progress(files.3 as u64);
     }
 
+    Ok(files)
+}
+
+/// Same as [from_adjacency_list], except the graph has at least `n_nodes` nodes, even if the last ones have no edges.
+/// This is used when deriving graphs whose node count is known beforehand.
+/// Fails with `InvalidData` if `n_nodes` is 0, since the vertex file always holds at least one node.
+pub fn from_sorted_edges<N, T>(
+    stream: T,
+    n_nodes: usize,
+    destination_folder_name: &str,
+) -> std::io::Result<GraphFiles>
+where
+    T: Iterator<Item = std::io::Result<(N, N)>> + Sized,
+    N: util::ValidGraphType,
+{
+    if n_nodes == 0 {
+        Err(std::io::ErrorKind::InvalidData)?;
+    }
+
+    let files = write_adjacency_list(
+        stream,
+        destination_folder_name,
+        VERTEX_NAME,
+        EDGE_NAME,
+        ConstructionHint::CheckSorted,
+        n_nodes,
+        true,
+    )?;
+
+    GraphMetadata::new::<N>(files.2 - 1, files.3)
+        .with_edges_sorted_per_node(true)
+        .save(destination_folder_name)?;
+
+    Ok(files)
+}
+
+/// Same as [from_adjacency_list], except the vertex and edge files are named `vertex_name` and `edge_name`,
+/// the sort check is only performed if `hint` asks for it, and the graph is padded to at least `min_nodes` nodes.
+/// If `sort_per_node` is set, the edges of each source are sorted by destination before being written,
+/// which only requires holding the edges of one source in memory at a time.
+pub fn write_adjacency_list<N, T>(
+    stream: T,
+    destination_folder_name: &str,
+    vertex_name: &str,
+    edge_name: &str,
+    hint: ConstructionHint,
+    min_nodes: usize,
+    sort_per_node: bool,
+) -> std::io::Result<GraphFiles>
+where
+    T: Iterator<Item = std::io::Result<(N, N)>> + Sized,
+    N: util::ValidGraphType,
+{
+    create_folder(destination_folder_name)?;
+
     // Create the files and buffers to write the data to
-    let nodes_file = get_vertex_file(destination_folder_name)?;
-    let edges_file = get_edge_file(destination_folder_name)?;
+    let nodes_file = get_file(destination_folder_name, vertex_name)?;
+    let edges_file = get_file(destination_folder_name, edge_name)?;
+    // A reused folder may hold the files of a larger graph
+    nodes_file.set_len(0)?;
+    edges_file.set_len(0)?;
     let mut nodes_writer = BufWriter::new(&nodes_file);
     let mut edges_writer = BufWriter::new(&edges_file);
 
     let mut previous_node = N::zero();
     let mut edges_count = 0usize;
     let mut max = 0usize;
+    let check_sorted = hint == ConstructionHint::CheckSorted;
+
+    // The destinations of `previous_node` that were not written yet
+    let mut pending = Vec::new();
+    let flush = |pending: &mut Vec<N>, edges_writer: &mut BufWriter<&fs::File>| {
+        if sort_per_node {
+            pending.sort_unstable_by_key(|dst| dst.as_());
+        }
+        for dst in pending.drain(..) {
+            edges_writer.write_all(&dst.serialize())?;
+        }
+        std::io::Result::Ok(())
+    };
 
     nodes_writer
-        .write(&0usize.to_ne_bytes())
+        .write_all(&0usize.to_ne_bytes())
         .expect("Failed to write first node");
 
     for e in stream {
@@ -71,31 +239,37 @@ where
         }
 
         // Check if sorted by source
-        if src < previous_node {
+        if check_sorted && src < previous_node {
             Err(std::io::ErrorKind::InvalidData)?;
         }
 
-        // Write edge to edge list
-        edges_writer.write(&dst.serialize())?;
+        // All edges of the previous source were seen, so they can be written to the edge list
+        if src != previous_node {
+            flush(&mut pending, &mut edges_writer)?;
+        }
+        pending.push(dst);
 
         // Write missing nodes
         while previous_node < src {
             previous_node = previous_node + N::one();
-            nodes_writer.write(&edges_count.to_ne_bytes())?;
+            nodes_writer.write_all(&edges_count.to_ne_bytes())?;
         }
 
-        edges_count = edges_count + 1;
+        edges_count += 1;
         previous_node = src;
     }
 
-    let max = max + 1;
+    flush(&mut pending, &mut edges_writer)?;
+
+    // The last source may be larger than any destination
+    let max = (max.max(previous_node.as_()) + 1).max(min_nodes);
 
     // Add nodes until we reach the max node
     let mut previous_node = previous_node.as_();
 
     while previous_node < max {
-        previous_node = previous_node + 1;
-        nodes_writer.write(&edges_count.to_ne_bytes())?;
+        previous_node += 1;
+        nodes_writer.write_all(&edges_count.to_ne_bytes())?;
     }
 
     edges_writer.flush()?;
@@ -107,6 +281,223 @@ where
     Ok(GraphFiles(nodes_file, edges_file, max + 1, edges_count))
 }
 
+/// Same as [write_adjacency_list], except the edges are already in memory and the CSR arrays are built on all threads.
+/// The edges are split into chunks covering disjoint ranges of sources, whose offsets and per-node sorted destinations are
+/// built in parallel, and then stitched together by shifting the offsets of each chunk by the edges before it.
+/// The edges of each node are always sorted by destination.
+pub fn write_adjacency_list_parallel<N>(
+    edges: Vec<(N, N)>,
+    destination_folder_name: &str,
+    vertex_name: &str,
+    edge_name: &str,
+    hint: ConstructionHint,
+) -> std::io::Result<GraphFiles>
+where
+    N: util::ValidGraphType + Send + Sync,
+{
+    if hint == ConstructionHint::CheckSorted && edges.par_windows(2).any(|w| w[1].0 < w[0].0) {
+        Err(std::io::ErrorKind::InvalidData)?;
+    }
+
+    let n_nodes = edges
+        .par_iter()
+        .map(|(src, dst)| src.as_().max(dst.as_()) + 1)
+        .max()
+        .unwrap_or(1);
+
+    // The first source of each chunk, so that chunks hold roughly the same number of edges
+    let n_chunks = rayon::current_num_threads().max(1);
+    let mut bounds = (0..n_chunks)
+        .map(|i| {
+            edges
+                .get(i * edges.len() / n_chunks)
+                .map_or(0, |e| e.0.as_())
+        })
+        .collect::<Vec<_>>();
+    bounds[0] = 0;
+    bounds.push(n_nodes);
+    bounds.sort_unstable();
+    bounds.dedup();
+
+    // The offsets of each chunk are relative to its first edge
+    let chunks = bounds
+        .par_windows(2)
+        .map(|w| {
+            let start = edges.partition_point(|e| e.0.as_() < w[0]);
+            let end = edges.partition_point(|e| e.0.as_() < w[1]);
+            let chunk = &edges[start..end];
+
+            let mut offsets = Vec::with_capacity(w[1] - w[0]);
+            let mut destinations = Vec::with_capacity(chunk.len());
+            let mut i = 0;
+            for node in w[0]..w[1] {
+                offsets.push(i);
+                while i < chunk.len() && chunk[i].0.as_() == node {
+                    destinations.push(chunk[i].1);
+                    i += 1;
+                }
+                destinations[offsets[offsets.len() - 1]..].sort_unstable_by_key(|dst| dst.as_());
+            }
+
+            (start, offsets, destinations)
+        })
+        .collect::<Vec<_>>();
+
+    create_folder(destination_folder_name)?;
+
+    let nodes_file = get_file(destination_folder_name, vertex_name)?;
+    let edges_file = get_file(destination_folder_name, edge_name)?;
+    nodes_file.set_len(0)?;
+    edges_file.set_len(0)?;
+    let mut nodes_writer = BufWriter::new(&nodes_file);
+    let mut edges_writer = BufWriter::new(&edges_file);
+
+    for (start, offsets, destinations) in chunks.iter() {
+        for offset in offsets {
+            nodes_writer.write_all(&(start + offset).to_ne_bytes())?;
+        }
+        for dst in destinations {
+            edges_writer.write_all(&dst.serialize())?;
+        }
+    }
+    nodes_writer.write_all(&edges.len().to_ne_bytes())?;
+
+    edges_writer.flush()?;
+    nodes_writer.flush()?;
+
+    drop(edges_writer);
+    drop(nodes_writer);
+
+    Ok(GraphFiles(nodes_file, edges_file, n_nodes + 1, edges.len()))
+}
+
+/// Checks that `nodes` and `edges` describe a valid CSR: `nodes` starts at 0, is non-decreasing, ends at `edges.len()`,
+/// and every edge points to one of the `nodes.len() - 1` nodes. Otherwise, `InvalidData` is returned.
+pub fn validate_csr_arrays<N>(nodes: &[usize], edges: &[N]) -> Result<()>
+where
+    N: util::ValidGraphType,
+{
+    if nodes.len() < 2
+        || nodes[0] != 0
+        || nodes.windows(2).any(|w| w[0] > w[1])
+        || nodes[nodes.len() - 1] != edges.len()
+        || edges.iter().any(|e| e.as_() >= nodes.len() - 1)
+    {
+        Err(std::io::ErrorKind::InvalidData)?;
+    }
+
+    Ok(())
+}
+
+/// Writes CSR arrays that are already built in memory directly to the vertex and edge files in `destination_folder_name`.
+/// The arrays are checked with [validate_csr_arrays] before anything is written.
+pub fn write_csr_arrays<N>(
+    nodes: &[usize],
+    edges: &[N],
+    destination_folder_name: &str,
+) -> Result<GraphFiles>
+where
+    N: util::ValidGraphType,
+{
+    validate_csr_arrays(nodes, edges)?;
+
+    create_folder(destination_folder_name)?;
+
+    let nodes_file = get_vertex_file(destination_folder_name)?;
+    let edges_file = get_edge_file(destination_folder_name)?;
+    nodes_file.set_len(0)?;
+    edges_file.set_len(0)?;
+
+    let mut nodes_writer = BufWriter::new(&nodes_file);
+    for node in nodes {
+        nodes_writer.write_all(&node.to_ne_bytes())?;
+    }
+    nodes_writer.flush()?;
+
+    let mut edges_writer = BufWriter::new(&edges_file);
+    for edge in edges {
+        edges_writer.write_all(&edge.serialize())?;
+    }
+    edges_writer.flush()?;
+
+    drop(edges_writer);
+    drop(nodes_writer);
+
+    let sorted = nodes
+        .windows(2)
+        .all(|w| edges[w[0]..w[1]].windows(2).all(|e| e[0] <= e[1]));
+    GraphMetadata::new::<N>(nodes.len() - 1, edges.len())
+        .with_edges_sorted_per_node(sorted)
+        .save(destination_folder_name)?;
+
+    Ok(GraphFiles(nodes_file, edges_file, nodes.len(), edges.len()))
+}
+
+/// Converts a binary adjacency file of the Ligra and GAP benchmarks into the vertex and edge files in `destination_folder_name`.
+/// The file holds native-endian 64-bit values: the number of nodes `n`, the number of edges `m`, the `n` offsets of the
+/// edge list of each node, and the `m` destinations. As this is already a CSR layout, the data is streamed straight to the output files.
+pub fn ligra_binary_to_csr<N>(adj_file: &str, destination_folder_name: &str) -> Result<GraphFiles>
+where
+    N: util::ValidGraphType,
+{
+    let file = fs::File::open(adj_file)?;
+    let file_len = file.metadata()?.len();
+    let mut reader = BufReader::new(file);
+
+    // The header must describe exactly the contents of the file
+    let n_nodes = reader.read_u64::<NativeEndian>()?;
+    let n_edges = reader.read_u64::<NativeEndian>()?;
+    let expected_len = n_nodes
+        .checked_add(n_edges)
+        .and_then(|len| len.checked_mul(8))
+        .and_then(|len| len.checked_add(16));
+    if expected_len != Some(file_len) {
+        Err(std::io::ErrorKind::InvalidData)?;
+    }
+    let (n_nodes, n_edges) = (n_nodes as usize, n_edges as usize);
+
+    create_folder(destination_folder_name)?;
+
+    let nodes_file = get_vertex_file(destination_folder_name)?;
+    let edges_file = get_edge_file(destination_folder_name)?;
+    nodes_file.set_len(0)?;
+    edges_file.set_len(0)?;
+
+    let mut nodes_writer = BufWriter::new(&nodes_file);
+    let mut previous = 0;
+    for i in 0..n_nodes {
+        let offset = reader.read_u64::<NativeEndian>()? as usize;
+        if (i == 0 && offset != 0) || offset < previous || offset > n_edges {
+            Err(std::io::ErrorKind::InvalidData)?;
+        }
+
+        nodes_writer.write_all(&offset.to_ne_bytes())?;
+        previous = offset;
+    }
+    // The last offset is implicit in the file
+    nodes_writer.write_all(&n_edges.to_ne_bytes())?;
+    nodes_writer.flush()?;
+
+    let mut edges_writer = BufWriter::new(&edges_file);
+    for _ in 0..n_edges {
+        let edge = reader.read_u64::<NativeEndian>()? as usize;
+        if edge >= n_nodes {
+            Err(std::io::ErrorKind::InvalidData)?;
+        }
+
+        let edge = N::from_usize(edge).ok_or(std::io::ErrorKind::InvalidData)?;
+        edges_writer.write_all(&edge.serialize())?;
+    }
+    edges_writer.flush()?;
+
+    drop(edges_writer);
+    drop(nodes_writer);
+
+    GraphMetadata::new::<N>(n_nodes, n_edges).save(destination_folder_name)?;
+
+    Ok(GraphFiles(nodes_file, edges_file, n_nodes + 1, n_edges))
+}
+
 /// This struct can be used to parse a binary reader into pairs of (T, T).
 pub struct ReaderIterator<T, K>
 where
@@ -115,11 +506,24 @@ where
 {
     reader: BufReader<K>,
     buffer: Vec<u8>,
+    endianness: util::Endianness,
     _phantom: PhantomData<T>,
 }
 
 /// Creates a new ReaderIterator from `reader` that yields pairs (T,T). K must be a type that implements the `Read` trait.
 pub fn reader_to_iter<T, K>(reader: K) -> ReaderIterator<T, impl Read>
+where
+    T: Sized + util::ValidGraphType,
+    K: Read,
+{
+    reader_to_iter_with_endianness(reader, util::Endianness::Native)
+}
+
+/// Same as [reader_to_iter], except the numbers in `reader` are in the given byte order.
+pub fn reader_to_iter_with_endianness<T, K>(
+    reader: K,
+    endianness: util::Endianness,
+) -> ReaderIterator<T, impl Read>
 where
     T: Sized + util::ValidGraphType,
     K: Read,
@@ -128,6 +532,7 @@ where
         reader: BufReader::new(reader),
         _phantom: PhantomData,
         buffer: vec![0u8; std::mem::size_of::<T>()],
+        endianness,
     }
 }
 
@@ -140,12 +545,12 @@ where
 
     fn next(&mut self) -> Option<Self::Item> {
         let v1 = match self.reader.read_exact(&mut self.buffer) {
-            Ok(_) => Some(T::from_bytes(&self.buffer)),
+            Ok(_) => Some(self.endianness.read(&self.buffer)),
             Err(_) => None,
         };
 
         let v2 = match self.reader.read_exact(&mut self.buffer) {
-            Ok(_) => Some(T::from_bytes(&self.buffer)),
+            Ok(_) => Some(self.endianness.read(&self.buffer)),
             Err(_) => None,
         };
 
@@ -155,3 +560,507 @@ where
         }
     }
 }
+
+/// Parses a line of a text adjacency list, which holds the source and the destination separated by whitespace.
+/// Fails with `InvalidData` if either number is missing or cannot be parsed.
+pub fn parse_txt_edge<N>(line: &str) -> Result<(N, N)>
+where
+    N: util::ValidGraphType,
+{
+    let mut parts = line.split_whitespace();
+
+    let src = parts
+        .next()
+        .ok_or(std::io::ErrorKind::InvalidData)?
+        .parse::<N>()
+        .or(Err(std::io::ErrorKind::InvalidData))?;
+
+    let dst = parts
+        .next()
+        .ok_or(std::io::ErrorKind::InvalidData)?
+        .parse::<N>()
+        .or(Err(std::io::ErrorKind::InvalidData))?;
+
+    Ok((src, dst))
+}
+
+/// Checks whether the text adjacency list in `stream` is sorted by source, as required to construct a graph from it,
+/// without writing anything. The scan stops at the first edge out of order, which is returned as [error::GraphError::UnsortedEdge].
+/// Fails as well if a line cannot be read or parsed, see [parse_txt_edge].
+pub fn check_sorted<N, T>(stream: T) -> std::result::Result<(), error::GraphError>
+where
+    N: util::ValidGraphType,
+    T: Read,
+{
+    let mut previous = None;
+
+    for (idx, line) in BufReader::new(stream).lines().enumerate() {
+        let line = line?;
+        let (src, _) = parse_txt_edge::<N>(&line)?;
+
+        if let Some(previous) = previous.filter(|previous| src < *previous) {
+            Err(error::GraphError::UnsortedEdge {
+                line: idx + 1,
+                content: line.trim().to_string(),
+                previous_source: previous.to_string(),
+            })?;
+        }
+        previous = Some(src);
+    }
+
+    Ok(())
+}
+
+/// Splits `bytes` into at most `n_chunks` chunks of about the same size, each ending right after a newline or at the end of `bytes`,
+/// so that no line is split between two chunks.
+pub fn split_at_lines(bytes: &[u8], n_chunks: usize) -> Vec<&[u8]> {
+    let chunk_len = bytes.len().div_ceil(n_chunks.max(1)).max(1);
+    let mut chunks = Vec::with_capacity(n_chunks);
+    let mut start = 0;
+
+    while start < bytes.len() {
+        let end = (start + chunk_len).min(bytes.len());
+        let end = bytes[end - 1..]
+            .iter()
+            .position(|byte| *byte == b'\n')
+            .map_or(bytes.len(), |newline| end + newline);
+
+        chunks.push(&bytes[start..end]);
+        start = end;
+    }
+
+    chunks
+}
+
+/// Merges runs of edges that are each sorted by source and destination into a single sorted stream.
+pub fn merge_sorted_runs<N>(runs: Vec<Vec<(N, N)>>) -> impl Iterator<Item = (N, N)>
+where
+    N: util::ValidGraphType,
+{
+    // The keys of the next edge of every run that is not exhausted, so the smallest one is on top
+    let mut heap = runs
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, run)| run.first().map(|(src, dst)| (src.as_(), dst.as_(), idx)))
+        .map(std::cmp::Reverse)
+        .collect::<std::collections::BinaryHeap<_>>();
+    let mut positions = vec![0; runs.len()];
+
+    std::iter::from_fn(move || {
+        let std::cmp::Reverse((_, _, idx)) = heap.pop()?;
+        let edge = runs[idx][positions[idx]];
+
+        positions[idx] += 1;
+        if let Some((src, dst)) = runs[idx].get(positions[idx]) {
+            heap.push(std::cmp::Reverse((src.as_(), dst.as_(), idx)));
+        }
+
+        Some(edge)
+    })
+}
+
+/// Sorts `stream` by source and destination without holding it in memory. The edges are split into chunks of about
+/// `chunk_size_bytes`, each chunk is sorted in memory and written to its own file in `temp_dir`, and the returned iterator
+/// merges all these sorted runs. The files are removed once the iterator is dropped.
+pub fn external_sort<N, T>(
+    stream: T,
+    chunk_size_bytes: usize,
+    temp_dir: &str,
+) -> Result<ExternalSortRuns<N>>
+where
+    N: util::ValidGraphType,
+    T: Iterator<Item = Result<(N, N)>> + Sized,
+{
+    let chunk_len = (chunk_size_bytes / (2 * std::mem::size_of::<N>())).max(1);
+    let prefix = format!("{}/graph_csr_sort_{}", temp_dir, rand::random::<u32>());
+
+    let mut runs = ExternalSortRuns {
+        runs: Vec::new(),
+        paths: Vec::new(),
+        heads: Vec::new(),
+        heap: std::collections::BinaryHeap::new(),
+    };
+    let mut chunk = Vec::with_capacity(chunk_len);
+    let mut stream = stream.peekable();
+
+    // Sort phase, one file for every chunk
+    while stream.peek().is_some() {
+        chunk.clear();
+        for edge in stream.by_ref().take(chunk_len) {
+            chunk.push(edge?);
+        }
+        chunk.sort_unstable_by_key(|(src, dst)| (src.as_(), dst.as_()));
+
+        let path = format!("{}_{}.tmp", prefix, runs.paths.len());
+        runs.paths.push(path.clone());
+        let mut writer = BufWriter::new(fs::File::create(&path)?);
+        for (src, dst) in chunk.iter() {
+            writer.write_all(&src.serialize())?;
+            writer.write_all(&dst.serialize())?;
+        }
+        writer.flush()?;
+    }
+
+    // Merge phase, starting from the first edge of every run
+    for (idx, path) in runs.paths.iter().enumerate() {
+        let mut run = ReaderIterator::<N, fs::File> {
+            reader: BufReader::new(fs::File::open(path)?),
+            buffer: vec![0u8; std::mem::size_of::<N>()],
+            endianness: util::Endianness::Native,
+            _phantom: PhantomData,
+        };
+        let head = run.next();
+        if let Some((src, dst)) = head {
+            runs.heap
+                .push(std::cmp::Reverse((src.as_(), dst.as_(), idx)));
+        }
+        runs.heads.push(head);
+        runs.runs.push(run);
+    }
+
+    Ok(runs)
+}
+
+/// The sorted runs written by [external_sort], merged into a single sorted stream of edges.
+pub struct ExternalSortRuns<N>
+where
+    N: util::ValidGraphType,
+{
+    runs: Vec<ReaderIterator<N, fs::File>>,
+    paths: Vec<String>,
+    /// The next edge of every run
+    heads: Vec<Option<(N, N)>>,
+    /// The keys of the next edge of every run that is not exhausted, so the smallest one is on top
+    heap: std::collections::BinaryHeap<std::cmp::Reverse<(usize, usize, usize)>>,
+}
+
+impl<N> Iterator for ExternalSortRuns<N>
+where
+    N: util::ValidGraphType,
+{
+    type Item = Result<(N, N)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let std::cmp::Reverse((_, _, idx)) = self.heap.pop()?;
+
+        let next = self.runs[idx].next();
+        if let Some((src, dst)) = next {
+            self.heap
+                .push(std::cmp::Reverse((src.as_(), dst.as_(), idx)));
+        }
+
+        std::mem::replace(&mut self.heads[idx], next).map(Ok)
+    }
+}
+
+impl<N> Drop for ExternalSortRuns<N>
+where
+    N: util::ValidGraphType,
+{
+    fn drop(&mut self) {
+        for path in self.paths.iter() {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+/// Parses a single 1-indexed node identifier into its 0-indexed counterpart.
+fn parse_one_indexed<N>(value: Option<&str>) -> Result<N>
+where
+    N: util::ValidGraphType,
+{
+    let value = value
+        .ok_or(std::io::ErrorKind::InvalidData)?
+        .parse::<N>()
+        .or(Err(std::io::ErrorKind::InvalidData))?;
+
+    if value == N::zero() {
+        Err(std::io::ErrorKind::InvalidData)?;
+    }
+
+    Ok(value - N::one())
+}
+
+/// Reads a MatrixMarket coordinate stream and returns its number of nodes, i.e. the largest of its dimensions,
+/// and its entries as 0-indexed `(row, col)` edges, sorted by row.
+/// Both `general` and `symmetric` matrices are supported. For the latter, each off-diagonal entry yields both directions.
+/// Any value field (`real`, `integer`, `complex`) is ignored, as only the topology of the matrix is kept.
+pub fn matrix_market_entries<N, T>(stream: T) -> Result<(usize, Vec<(N, N)>)>
+where
+    N: util::ValidGraphType,
+    T: Read,
+{
+    let mut lines = BufReader::new(stream).lines();
+
+    // Parse the header, which describes the layout of the matrix
+    let header = lines.next().ok_or(std::io::ErrorKind::InvalidData)??;
+    let header = header.to_lowercase();
+    let header = header.split_whitespace().collect::<Vec<_>>();
+
+    let symmetric = match header.as_slice() {
+        ["%%matrixmarket", "matrix", "coordinate", _, "general"] => false,
+        ["%%matrixmarket", "matrix", "coordinate", _, "symmetric"] => true,
+        _ => return Err(std::io::ErrorKind::InvalidData.into()),
+    };
+
+    // Skip comments and empty lines
+    let mut lines = lines.filter(|line| match line {
+        Ok(line) => !line.starts_with('%') && !line.trim().is_empty(),
+        Err(_) => true,
+    });
+
+    // Parse the dimensions line
+    let dimensions = lines.next().ok_or(std::io::ErrorKind::InvalidData)??;
+    let dimensions = dimensions
+        .split_whitespace()
+        .map(|x| x.parse::<usize>())
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .or(Err(std::io::ErrorKind::InvalidData))?;
+
+    let (rows, cols, entries) = match dimensions.as_slice() {
+        [rows, cols, entries] => (*rows, *cols, *entries),
+        _ => return Err(std::io::ErrorKind::InvalidData.into()),
+    };
+
+    // The number of entries comes from the stream, so it only bounds the initial allocation
+    let mut edges = Vec::with_capacity(entries.min(1 << 20));
+
+    for line in lines {
+        let line = line?;
+        let mut parts = line.split_whitespace();
+
+        let row = parse_one_indexed::<N>(parts.next())?;
+        let col = parse_one_indexed::<N>(parts.next())?;
+
+        if row.as_() >= rows || col.as_() >= cols {
+            Err(std::io::ErrorKind::InvalidData)?;
+        }
+
+        edges.push((row, col));
+        if symmetric && row != col {
+            edges.push((col, row));
+        }
+    }
+
+    edges.sort_unstable_by_key(|(src, dst)| (src.as_(), dst.as_()));
+
+    Ok((rows.max(cols), edges))
+}
+
+/// Converts a 1-indexed node identifier into a 0-indexed `N`, checking that it does not exceed `n_nodes`.
+fn dimacs_node<N>(value: usize, n_nodes: usize) -> Result<N>
+where
+    N: util::ValidGraphType,
+{
+    if value == 0 || value > n_nodes {
+        Err(std::io::ErrorKind::InvalidData)?;
+    }
+
+    N::from_usize(value - 1).ok_or_else(|| std::io::ErrorKind::InvalidData.into())
+}
+
+/// The section of a Pajek file that is being read.
+enum PajekSection {
+    Header,
+    Vertices,
+    Arcs {
+        list: bool,
+        undirected: bool,
+    },
+    /// A section this reader does not support, e.g. `*Partition` or `*Vector`, whose lines are skipped.
+    Unknown,
+}
+
+/// Reads a Pajek `.net` stream and returns its number of nodes and its arcs as 0-indexed `(src, dst)` edges, sorted by source.
+/// The `*Vertices N` section is skipped, as vertex labels and coordinates are not kept.
+/// `*Arcs` and `*Arcslist` sections yield directed edges, while `*Edges` and `*Edgeslist` sections yield both directions.
+/// The `*Network` header is skipped, and so are the lines of any section that is not about vertices or arcs, e.g. `*Partition`.
+/// Weights in the third column of arc and edge lines must be numbers, but are dropped, as only the topology of the graph is kept.
+pub fn pajek_entries<N, T>(stream: T) -> Result<(usize, Vec<(N, N)>)>
+where
+    N: util::ValidGraphType,
+    T: Read,
+{
+    let mut n_nodes = None;
+    let mut section = PajekSection::Header;
+    let mut edges = Vec::new();
+
+    for line in BufReader::new(stream).lines() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('%') {
+            continue;
+        }
+
+        if let Some(header) = line.strip_prefix('*') {
+            let mut parts = header.split_whitespace();
+            let name = parts
+                .next()
+                .ok_or(std::io::ErrorKind::InvalidData)?
+                .to_lowercase();
+
+            section = match name.as_str() {
+                "network" => continue,
+                // The values of a partition or a vector are listed in their own vertices section
+                "vertices" if matches!(section, PajekSection::Unknown) => PajekSection::Unknown,
+                "vertices" if n_nodes.is_none() => {
+                    n_nodes = Some(
+                        parts
+                            .next()
+                            .ok_or(std::io::ErrorKind::InvalidData)?
+                            .parse::<usize>()
+                            .or(Err(std::io::ErrorKind::InvalidData))?,
+                    );
+                    PajekSection::Vertices
+                }
+                "arcs" | "edges" | "arcslist" | "edgeslist" if n_nodes.is_some() => {
+                    PajekSection::Arcs {
+                        list: name.ends_with("list"),
+                        undirected: name.starts_with("edges"),
+                    }
+                }
+                "vertices" | "arcs" | "edges" | "arcslist" | "edgeslist" => {
+                    Err(std::io::ErrorKind::InvalidData)?
+                }
+                _ => PajekSection::Unknown,
+            };
+            continue;
+        }
+
+        let (list, undirected) = match section {
+            PajekSection::Header => Err(std::io::ErrorKind::InvalidData)?,
+            PajekSection::Vertices | PajekSection::Unknown => continue,
+            PajekSection::Arcs { list, undirected } => (list, undirected),
+        };
+        let n_nodes = n_nodes.ok_or(std::io::ErrorKind::InvalidData)?;
+
+        let parse = |value: Option<&str>| {
+            dimacs_node::<N>(
+                value
+                    .ok_or(std::io::ErrorKind::InvalidData)?
+                    .parse::<usize>()
+                    .or(Err(std::io::ErrorKind::InvalidData))?,
+                n_nodes,
+            )
+        };
+
+        let mut parts = line.split_whitespace();
+        let src = parse(parts.next())?;
+        let destinations = if list {
+            parts
+                .map(|dst| parse(Some(dst)))
+                .collect::<Result<Vec<_>>>()?
+        } else {
+            let dst = parse(parts.next())?;
+            // The weight is optional, but a malformed one means the line is not what it seems
+            if let Some(weight) = parts.next() {
+                weight
+                    .parse::<f64>()
+                    .or(Err(std::io::ErrorKind::InvalidData))?;
+            }
+            vec![dst]
+        };
+
+        for dst in destinations {
+            edges.push((src, dst));
+            if undirected && src != dst {
+                edges.push((dst, src));
+            }
+        }
+    }
+
+    edges.sort_unstable_by_key(|(src, dst)| (src.as_(), dst.as_()));
+
+    Ok((n_nodes.ok_or(std::io::ErrorKind::InvalidData)?, edges))
+}
+
+/// Reads a binary DIMACS stream and returns its arcs as 0-indexed `(src, dst)` edges, sorted by source.
+/// Each record is made of four native-endian `u32` values: the [DIMACS_SENTINEL], the 1-indexed source and destination, and the weight.
+/// Weights are ignored, as only the topology of the graph is kept. Fails with `InvalidData` if a node exceeds `n_nodes`.
+pub fn dimacs_binary_entries<N, T>(stream: T, n_nodes: usize) -> Result<Vec<(N, N)>>
+where
+    N: util::ValidGraphType,
+    T: Read,
+{
+    let mut reader = BufReader::new(stream);
+    let mut edges = Vec::new();
+
+    // A record must either be complete or not start at all
+    while !reader.fill_buf()?.is_empty() {
+        if reader.read_u32::<NativeEndian>()? != DIMACS_SENTINEL {
+            Err(std::io::ErrorKind::InvalidData)?;
+        }
+
+        let src = reader.read_u32::<NativeEndian>()? as usize;
+        let dst = reader.read_u32::<NativeEndian>()? as usize;
+        let _weight = reader.read_u32::<NativeEndian>()?;
+
+        edges.push((
+            dimacs_node::<N>(src, n_nodes)?,
+            dimacs_node::<N>(dst, n_nodes)?,
+        ));
+    }
+
+    edges.sort_unstable_by_key(|(src, dst)| (src.as_(), dst.as_()));
+
+    Ok(edges)
+}
+
+/// Reads a DIMACS shortest path text stream and returns its number of nodes and its arcs as 0-indexed `(src, dst)` edges, sorted by source.
+/// The stream must contain a `p sp N M` problem line before any `a U V W` arc line. Comment lines start with `c`.
+/// Weights are ignored, as only the topology of the graph is kept.
+pub fn dimacs_text_entries<N, T>(stream: T) -> Result<(usize, Vec<(N, N)>)>
+where
+    N: util::ValidGraphType,
+    T: Read,
+{
+    let mut n_nodes = None;
+    let mut edges = Vec::new();
+
+    for line in BufReader::new(stream).lines() {
+        let line = line?;
+        let mut parts = line.split_whitespace();
+
+        match parts.next() {
+            None | Some("c") => {}
+            Some("p") => {
+                if n_nodes.is_some() || parts.next() != Some("sp") {
+                    Err(std::io::ErrorKind::InvalidData)?;
+                }
+
+                let mut parse = || {
+                    parts
+                        .next()
+                        .ok_or(std::io::ErrorKind::InvalidData)?
+                        .parse::<usize>()
+                        .or(Err(std::io::ErrorKind::InvalidData))
+                };
+
+                n_nodes = Some(parse()?);
+                // The number of arcs comes from the stream, so it only bounds the initial allocation
+                edges.reserve(parse()?.min(1 << 20));
+            }
+            Some("a") => {
+                let n_nodes = n_nodes.ok_or(std::io::ErrorKind::InvalidData)?;
+
+                let mut parse = || {
+                    parts
+                        .next()
+                        .ok_or(std::io::ErrorKind::InvalidData)?
+                        .parse::<usize>()
+                        .or(Err(std::io::ErrorKind::InvalidData))
+                };
+
+                let src = dimacs_node::<N>(parse()?, n_nodes)?;
+                let dst = dimacs_node::<N>(parse()?, n_nodes)?;
+
+                edges.push((src, dst));
+            }
+            Some(_) => Err(std::io::ErrorKind::InvalidData)?,
+        }
+    }
+
+    edges.sort_unstable_by_key(|(src, dst)| (src.as_(), dst.as_()));
+
+    Ok((n_nodes.ok_or(std::io::ErrorKind::InvalidData)?, edges))
+}
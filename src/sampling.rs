@@ -0,0 +1,281 @@
+use rand::{rngs::StdRng, seq::index, Rng, SeedableRng};
+use rayon::prelude::*;
+
+use super::{util::ValidGraphType, Graph};
+
+/// What a random walk does when it reaches a node without outgoing edges.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DeadEndPolicy {
+    /// The walk ends at the dead end, so it can be shorter than requested.
+    #[default]
+    Stop,
+    /// The walk jumps back to its source and continues from there.
+    Restart,
+}
+
+impl<'a, N> Graph<'a, N>
+where
+    N: ValidGraphType,
+{
+    /// Draws `n` distinct edges uniformly at random, or all of them if the graph has less than `n` edges.
+    /// Each edge is drawn by picking a source with probability proportional to its out-degree, and then one of its edges uniformly,
+    /// which amounts to picking a position of the edge array and finding the node it belongs to.
+    pub fn sample_edges(&self, n: usize, rng: &mut impl Rng) -> Vec<(usize, N)> {
+        let (nodes, edges) = self.as_csr_slices();
+
+        index::sample(rng, edges.len(), n.min(edges.len()))
+            .into_iter()
+            .map(|edge| {
+                // The source is the last node whose edges start at or before `edge`
+                let src = nodes.partition_point(|offset| *offset <= edge) - 1;
+                (src, edges[edge])
+            })
+            .collect()
+    }
+
+    /// Draws `k` distinct neighbors of `node` uniformly at random by reservoir sampling, or all of them if it has less than `k`.
+    pub fn neighbor_sample(&self, node: usize, k: usize, rng: &mut impl Rng) -> Vec<N> {
+        let neighbors = self.neighbors(node);
+        let mut reservoir = neighbors[..k.min(neighbors.len())].to_vec();
+
+        for (i, neighbor) in neighbors.iter().enumerate().skip(k) {
+            let j = rng.gen_range(0..=i);
+            if j < k {
+                reservoir[j] = *neighbor;
+            }
+        }
+
+        reservoir
+    }
+
+    /// Draws `k` neighbors of `node` uniformly at random, where the same neighbor can be drawn more than once.
+    /// Returns an empty sample if `node` has no neighbors.
+    pub fn neighbor_sample_with_replacement(
+        &self,
+        node: usize,
+        k: usize,
+        rng: &mut impl Rng,
+    ) -> Vec<N> {
+        let neighbors = self.neighbors(node);
+        if neighbors.is_empty() {
+            return Vec::new();
+        }
+
+        (0..k)
+            .map(|_| neighbors[rng.gen_range(0..neighbors.len())])
+            .collect()
+    }
+
+    /// Walks `length` steps from `source`, moving to a neighbor of the current node chosen uniformly at random at each step.
+    /// Returns the visited nodes, starting with `source`, so a complete walk has `length + 1` nodes.
+    pub fn random_walk(
+        &self,
+        source: usize,
+        length: usize,
+        policy: DeadEndPolicy,
+        rng: &mut impl Rng,
+    ) -> Vec<usize> {
+        let mut walk = Vec::with_capacity(length + 1);
+        walk.push(source);
+
+        let mut current = source;
+        for _ in 0..length {
+            let neighbors = self.neighbors(current);
+            current = if neighbors.is_empty() {
+                match policy {
+                    DeadEndPolicy::Stop => break,
+                    DeadEndPolicy::Restart => source,
+                }
+            } else {
+                neighbors[rng.gen_range(0..neighbors.len())].as_()
+            };
+            walk.push(current);
+        }
+
+        walk
+    }
+
+    /// Same as [Graph::random_walk], for each of `sources` in parallel.
+    /// Each walk uses its own generator seeded from `rng`, so the result only depends on the state of `rng`.
+    pub fn random_walks(
+        &self,
+        sources: &[usize],
+        length: usize,
+        policy: DeadEndPolicy,
+        rng: &mut impl Rng,
+    ) -> Vec<Vec<usize>>
+    where
+        N: Send + Sync,
+    {
+        let seeds = sources.iter().map(|_| rng.gen()).collect::<Vec<u64>>();
+
+        sources
+            .par_iter()
+            .zip(seeds)
+            .map(|(source, seed)| {
+                self.random_walk(*source, length, policy, &mut StdRng::seed_from_u64(seed))
+            })
+            .collect()
+    }
+
+    /// Draws `n` distinct nodes uniformly at random, or all of them if the graph has less than `n` nodes.
+    pub fn sample_nodes(&self, n: usize, rng: &mut impl Rng) -> Vec<usize> {
+        index::sample(rng, self.n_nodes(), n.min(self.n_nodes())).into_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_basic_graph<'a>() -> Graph<'a, u32> {
+        Graph::from_adjacency_list(
+            vec![(0u32, 1u32), (0, 2), (1, 5), (1, 2), (4, 7)]
+                .into_iter()
+                .map(Ok),
+            &format!("/tmp/tmp_dst_{}", rand::random::<u32>()),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn sample_edges() {
+        let graph = get_basic_graph();
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let mut sample = graph.sample_edges(3, &mut rng);
+        assert_eq!(sample.len(), 3);
+        sample.sort_unstable();
+        sample.dedup();
+        assert_eq!(sample.len(), 3);
+        for (src, dst) in sample {
+            assert!(graph.neighbors(src).contains(&dst));
+        }
+
+        let mut all = graph.sample_edges(100, &mut rng);
+        all.sort_unstable();
+        assert_eq!(all, vec![(0, 1), (0, 2), (1, 2), (1, 5), (4, 7)]);
+
+        // Every edge is equally likely, even though node 4 has a single one
+        let mut counts = [0; 8];
+        for _ in 0..5000 {
+            counts[graph.sample_edges(1, &mut rng)[0].0] += 1;
+        }
+        assert!((1700..2300).contains(&counts[0]));
+        assert!((800..1200).contains(&counts[4]));
+    }
+
+    #[test]
+    fn neighbor_sample() {
+        let edges = (0..10u32).map(|dst| Ok((0u32, dst)));
+        let graph =
+            Graph::from_adjacency_list(edges, &format!("/tmp/tmp_dst_{}", rand::random::<u32>()))
+                .unwrap();
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let mut sample = graph.neighbor_sample(0, 4, &mut rng);
+        assert_eq!(sample.len(), 4);
+        sample.sort_unstable();
+        sample.dedup();
+        assert_eq!(sample.len(), 4);
+
+        let mut all = graph.neighbor_sample(0, 20, &mut rng);
+        all.sort_unstable();
+        assert_eq!(all, (0..10).collect::<Vec<_>>());
+        assert!(graph.neighbor_sample(3, 2, &mut rng).is_empty());
+
+        // Every neighbor is equally likely to be kept
+        let mut counts = [0; 10];
+        for _ in 0..5000 {
+            for neighbor in graph.neighbor_sample(0, 2, &mut rng) {
+                counts[neighbor as usize] += 1;
+            }
+        }
+        assert!(counts.iter().all(|count| (800..1200).contains(count)));
+    }
+
+    #[test]
+    fn neighbor_sample_with_replacement() {
+        let graph = get_basic_graph();
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let sample = graph.neighbor_sample_with_replacement(4, 5, &mut rng);
+        assert_eq!(sample, vec![7; 5]);
+
+        let sample = graph.neighbor_sample_with_replacement(0, 10, &mut rng);
+        assert_eq!(sample.len(), 10);
+        assert!(sample.iter().all(|x| [1, 2].contains(x)));
+
+        assert!(graph
+            .neighbor_sample_with_replacement(3, 5, &mut rng)
+            .is_empty());
+    }
+
+    #[test]
+    fn random_walk() {
+        let graph = get_basic_graph();
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let walk = graph.random_walk(0, 10, DeadEndPolicy::Stop, &mut rng);
+        assert_eq!(walk[0], 0);
+        for step in walk.windows(2) {
+            assert!(graph.neighbors(step[0]).contains(&(step[1] as u32)));
+        }
+        // Every path from 0 reaches a dead end within two steps
+        assert!(walk.len() <= 3);
+        assert!(graph.neighbors(*walk.last().unwrap()).is_empty());
+
+        let walk = graph.random_walk(4, 6, DeadEndPolicy::Restart, &mut rng);
+        assert_eq!(walk, vec![4, 7, 4, 7, 4, 7, 4]);
+
+        assert_eq!(
+            graph.random_walk(3, 5, DeadEndPolicy::Stop, &mut rng),
+            vec![3]
+        );
+        assert_eq!(
+            graph.random_walk(0, 0, DeadEndPolicy::Stop, &mut rng),
+            vec![0]
+        );
+    }
+
+    #[test]
+    fn random_walks() {
+        let graph = get_basic_graph();
+        let sources = [0, 1, 4, 4];
+
+        let walks = graph.random_walks(
+            &sources,
+            4,
+            DeadEndPolicy::Restart,
+            &mut StdRng::seed_from_u64(7),
+        );
+        assert_eq!(walks.len(), sources.len());
+        for (walk, source) in walks.iter().zip(sources) {
+            assert_eq!(walk.len(), 5);
+            assert_eq!(walk[0], source);
+        }
+
+        // The same seed gives the same walks, regardless of scheduling
+        let again = graph.random_walks(
+            &sources,
+            4,
+            DeadEndPolicy::Restart,
+            &mut StdRng::seed_from_u64(7),
+        );
+        assert_eq!(walks, again);
+    }
+
+    #[test]
+    fn sample_nodes() {
+        let graph = get_basic_graph();
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let mut sample = graph.sample_nodes(5, &mut rng);
+        sample.sort_unstable();
+        sample.dedup();
+        assert_eq!(sample.len(), 5);
+        assert!(sample.iter().all(|node| *node < graph.n_nodes()));
+
+        assert_eq!(graph.sample_nodes(20, &mut rng).len(), graph.n_nodes());
+    }
+}
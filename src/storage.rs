@@ -0,0 +1,109 @@
+use std::{fs, ops::Index, os::unix::io::AsRawFd};
+
+use easy_mmap::{EasyMmap, EasyMmapBuilder, MapOption};
+use rayon::prelude::*;
+
+/// The memory backing each of the arrays of a graph.
+/// It exposes the same read-only accessors as [EasyMmap], so that the graph does not depend on where its data lives.
+pub enum Storage<'a, T> {
+    /// Memory mapped from a file, and loaded into memory as required.
+    Mapped(EasyMmap<'a, T>),
+    /// Allocated on the heap, without any disk backing.
+    Memory(Vec<T>),
+}
+
+// SAFETY: `EasyMmap` is neither `Send` nor `Sync` because it holds raw pointers to the mapping. `Storage` never gives out
+// mutable access to the mapped memory: its accessors only return shared slices, and `Graph` keeps its storages private.
+// The mapping is only released on drop, which requires ownership, so sharing or sending it is as safe as for a `Vec<T>`.
+unsafe impl<'a, T> Send for Storage<'a, T> where T: Send {}
+unsafe impl<'a, T> Sync for Storage<'a, T> where T: Sync {}
+
+impl<'a, T> Storage<'a, T>
+where
+    T: Copy,
+{
+    /// Memory maps the contents of `file` as an array of `T`.
+    /// Empty files cannot be memory mapped, so they are represented by an empty array in memory instead.
+    pub fn map_file(file: fs::File) -> Self {
+        let capacity = file
+            .metadata()
+            .expect("Failed to read metadata of file")
+            .len() as usize
+            / std::mem::size_of::<T>();
+
+        if capacity == 0 {
+            return Storage::Memory(Vec::new());
+        }
+
+        Storage::Mapped(
+            EasyMmapBuilder::<T>::new()
+                .capacity(capacity)
+                .file(file)
+                .readable()
+                .build(),
+        )
+    }
+
+    /// Same as [map_file](Self::map_file), except `file` only needs to be opened for reading.
+    /// The builder would resize a file it owns, which requires write access, so the map is created from the raw descriptor instead.
+    /// The mapping stays valid after `file` is closed at the end of this function.
+    pub fn map_file_readonly(file: fs::File) -> Self {
+        let capacity = file
+            .metadata()
+            .expect("Failed to read metadata of file")
+            .len() as usize
+            / std::mem::size_of::<T>();
+
+        if capacity == 0 {
+            return Storage::Memory(Vec::new());
+        }
+
+        Storage::Mapped(
+            EasyMmapBuilder::<T>::new()
+                .capacity(capacity)
+                .add_option(MapOption::MapFd(file.as_raw_fd()))
+                .readable()
+                .build(),
+        )
+    }
+
+    /// How many elements are stored.
+    pub fn len(&self) -> usize {
+        match self {
+            Storage::Mapped(mmap) => mmap.len(),
+            Storage::Memory(vec) => vec.len(),
+        }
+    }
+
+    /// Returns a read-only iterator over the elements.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.get_data_as_slice().iter()
+    }
+
+    /// Returns a parallel iterator over the elements.
+    pub fn par_iter(&self) -> impl ParallelIterator<Item = &T>
+    where
+        T: Send + Sync,
+    {
+        self.get_data_as_slice().par_iter()
+    }
+
+    /// Returns a read-only slice of the data.
+    pub fn get_data_as_slice(&self) -> &[T] {
+        match self {
+            Storage::Mapped(mmap) => mmap.get_data_as_slice(),
+            Storage::Memory(vec) => vec.as_slice(),
+        }
+    }
+}
+
+impl<'a, T> Index<usize> for Storage<'a, T>
+where
+    T: Copy,
+{
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.get_data_as_slice()[index]
+    }
+}
@@ -1,4 +1,8 @@
-use std::io::Write;
+use std::{
+    collections::HashSet,
+    fs,
+    io::{BufWriter, Write},
+};
 
 use byteorder::{NativeEndian, WriteBytesExt};
 
@@ -11,10 +15,36 @@ pub trait ValidGraphType:
     + std::fmt::Display
     + num_traits::Num
     + num_traits::AsPrimitive<usize>
+    + num_traits::FromPrimitive
     + std::cmp::PartialOrd
 {
     fn serialize(&self) -> Vec<u8>;
     fn from_bytes(bytes: &[u8]) -> Self;
+    fn from_bytes_le(bytes: &[u8]) -> Self;
+    fn from_bytes_be(bytes: &[u8]) -> Self;
+}
+
+/// The byte order of the numbers in a binary file.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Endianness {
+    /// The byte order of the machine running the program.
+    #[default]
+    Native,
+    /// Least significant byte first, as in x86.
+    Little,
+    /// Most significant byte first, as in network byte order.
+    Big,
+}
+
+impl Endianness {
+    /// Reads a `T` from the first bytes of `bytes` in this byte order.
+    pub fn read<T: ValidGraphType>(&self, bytes: &[u8]) -> T {
+        match self {
+            Endianness::Native => T::from_bytes(bytes),
+            Endianness::Little => T::from_bytes_le(bytes),
+            Endianness::Big => T::from_bytes_be(bytes),
+        }
+    }
 }
 
 impl ValidGraphType for u64 {
@@ -23,12 +53,22 @@ impl ValidGraphType for u64 {
     }
     fn from_bytes(bytes: &[u8]) -> Self {
         let mut arr = [0u8; std::mem::size_of::<Self>()];
-        for i in 0..std::mem::size_of::<Self>() {
-            arr[i] = bytes[i];
-        }
+        arr.copy_from_slice(&bytes[..std::mem::size_of::<Self>()]);
 
         u64::from_ne_bytes(arr)
     }
+    fn from_bytes_le(bytes: &[u8]) -> Self {
+        let mut arr = [0u8; std::mem::size_of::<Self>()];
+        arr.copy_from_slice(&bytes[..std::mem::size_of::<Self>()]);
+
+        u64::from_le_bytes(arr)
+    }
+    fn from_bytes_be(bytes: &[u8]) -> Self {
+        let mut arr = [0u8; std::mem::size_of::<Self>()];
+        arr.copy_from_slice(&bytes[..std::mem::size_of::<Self>()]);
+
+        u64::from_be_bytes(arr)
+    }
 }
 
 impl ValidGraphType for u32 {
@@ -37,12 +77,22 @@ impl ValidGraphType for u32 {
     }
     fn from_bytes(bytes: &[u8]) -> Self {
         let mut arr = [0u8; std::mem::size_of::<Self>()];
-        for i in 0..std::mem::size_of::<Self>() {
-            arr[i] = bytes[i];
-        }
+        arr.copy_from_slice(&bytes[..std::mem::size_of::<Self>()]);
 
         u32::from_ne_bytes(arr)
     }
+    fn from_bytes_le(bytes: &[u8]) -> Self {
+        let mut arr = [0u8; std::mem::size_of::<Self>()];
+        arr.copy_from_slice(&bytes[..std::mem::size_of::<Self>()]);
+
+        u32::from_le_bytes(arr)
+    }
+    fn from_bytes_be(bytes: &[u8]) -> Self {
+        let mut arr = [0u8; std::mem::size_of::<Self>()];
+        arr.copy_from_slice(&bytes[..std::mem::size_of::<Self>()]);
+
+        u32::from_be_bytes(arr)
+    }
 }
 
 /// The data present in each vertex
@@ -61,6 +111,11 @@ impl GraphData for u64 {
         writer.write_u64::<NativeEndian>(*self)
     }
 }
+impl GraphData for usize {
+    fn write_self(&self, writer: &mut impl Write) -> std::io::Result<()> {
+        writer.write_all(&self.to_ne_bytes())
+    }
+}
 impl GraphData for f32 {
     fn write_self(&self, writer: &mut impl Write) -> std::io::Result<()> {
         writer.write_f32::<NativeEndian>(*self)
@@ -71,3 +126,181 @@ impl GraphData for f64 {
         writer.write_f64::<NativeEndian>(*self)
     }
 }
+
+/// The name of the file that stores the node attributes of a graph inside its folder, see [Graph::save_attributes](crate::Graph::save_attributes).
+pub const ATTRIBUTE_NAME: &str = "node_attr.bin";
+
+/// The name of the file that stores a [NodeRenaming] inside a graph folder.
+pub const RENAMING_NAME: &str = "renaming.csr";
+
+/// Compresses a sparse node ID space into the contiguous range `0..len()`.
+/// When node IDs span a much larger range than the number of nodes present, renaming them before construction
+/// avoids storing huge amounts of phantom nodes without any edge.
+/// IDs are renamed in increasing order, so an edge list sorted by source remains sorted after [rename](Self::rename).
+/// The table can be stored in the graph folder with [save](Self::save), or serialized with the `serde` feature.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NodeRenaming<N> {
+    original: Vec<N>,
+}
+
+impl<N> NodeRenaming<N>
+where
+    N: ValidGraphType,
+{
+    /// First pass over the edge list, which collects all unique node IDs and assigns them contiguous indices.
+    pub fn compress<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = (N, N)>,
+    {
+        let mut seen = HashSet::new();
+        let mut original = Vec::new();
+
+        for (src, dst) in iter {
+            for id in [src, dst] {
+                if seen.insert(id.as_()) {
+                    original.push(id);
+                }
+            }
+        }
+
+        original.sort_unstable_by_key(|id| id.as_());
+
+        NodeRenaming { original }
+    }
+
+    /// Second pass over the edge list, which rewrites the edges with the new IDs.
+    /// Panics if an edge contains an ID that was not seen by [compress](Self::compress).
+    pub fn rename<'a, I>(&'a self, iter: I) -> impl Iterator<Item = (N, N)> + 'a
+    where
+        I: Iterator<Item = (N, N)> + 'a,
+    {
+        iter.map(move |(src, dst)| {
+            let src = self.original_to_new(src).expect("Unknown source node");
+            let dst = self.original_to_new(dst).expect("Unknown destination node");
+
+            (
+                N::from_usize(src).expect("Renamed node does not fit in N"),
+                N::from_usize(dst).expect("Renamed node does not fit in N"),
+            )
+        })
+    }
+
+    /// Returns the new index of the original node `id`, or `None` if it was not present in the edge list.
+    pub fn original_to_new(&self, id: N) -> Option<usize> {
+        self.original
+            .binary_search_by_key(&id.as_(), |x| x.as_())
+            .ok()
+    }
+
+    /// Returns the original ID of the node with the new index `idx`.
+    pub fn new_to_original(&self, idx: usize) -> N {
+        self.original[idx]
+    }
+
+    /// Returns the number of unique nodes.
+    pub fn len(&self) -> usize {
+        self.original.len()
+    }
+
+    /// Returns whether there are no nodes at all.
+    pub fn is_empty(&self) -> bool {
+        self.original.is_empty()
+    }
+
+    /// Writes the table into [RENAMING_NAME] inside `folder_name`, as the original ID of each new index in binary format.
+    pub fn save(&self, folder_name: &str) -> std::io::Result<()> {
+        let mut writer = BufWriter::new(fs::File::create(format!(
+            "{}/{}",
+            folder_name, RENAMING_NAME
+        ))?);
+        for id in self.original.iter() {
+            writer.write_all(&id.serialize())?;
+        }
+
+        writer.flush()
+    }
+
+    /// Reads a table written by [save](Self::save) from `folder_name`, avoiding a new pass over the edge list.
+    /// Returns `InvalidData` if the file is not a valid table of node type `N`.
+    pub fn load(folder_name: &str) -> std::io::Result<Self> {
+        let bytes = fs::read(format!("{}/{}", folder_name, RENAMING_NAME))?;
+        let size = std::mem::size_of::<N>();
+        if bytes.len() % size != 0 {
+            Err(std::io::ErrorKind::InvalidData)?;
+        }
+
+        let original = bytes
+            .chunks_exact(size)
+            .map(N::from_bytes)
+            .collect::<Vec<_>>();
+        if original.windows(2).any(|w| w[0].as_() >= w[1].as_()) {
+            Err(std::io::ErrorKind::InvalidData)?;
+        }
+
+        Ok(NodeRenaming { original })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn endianness() {
+        let bytes = [0x01, 0x02, 0x03, 0x04];
+
+        assert_eq!(Endianness::Little.read::<u32>(&bytes), 0x04030201);
+        assert_eq!(Endianness::Big.read::<u32>(&bytes), 0x01020304);
+        assert_eq!(
+            Endianness::Native.read::<u32>(&bytes),
+            u32::from_ne_bytes(bytes)
+        );
+        assert_eq!(Endianness::Big.read::<u64>(&[0, 0, 0, 0, 0, 0, 1, 0]), 256);
+    }
+
+    #[test]
+    fn node_renaming() {
+        let edges = vec![(10u64, 1_000_000u64), (10, 7), (500, 10), (1_000_000, 7)];
+
+        let renaming = NodeRenaming::compress(edges.iter().copied());
+
+        assert_eq!(renaming.len(), 4);
+        assert_eq!(renaming.original_to_new(7), Some(0));
+        assert_eq!(renaming.original_to_new(1_000_000), Some(3));
+        assert_eq!(renaming.original_to_new(8), None);
+        assert_eq!(renaming.new_to_original(2), 500);
+
+        assert_eq!(
+            renaming.rename(edges.into_iter()).collect::<Vec<_>>(),
+            vec![(1, 3), (1, 0), (2, 1), (3, 0)]
+        );
+    }
+
+    #[test]
+    fn node_renaming_save_and_load() {
+        let folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+        fs::create_dir(&folder_name).unwrap();
+
+        let renaming = NodeRenaming::compress([(10u64, 1_000_000u64), (500, 7)].into_iter());
+        renaming.save(&folder_name).unwrap();
+
+        let loaded = NodeRenaming::<u64>::load(&folder_name).unwrap();
+        assert_eq!(loaded.original, vec![7, 10, 500, 1_000_000]);
+        assert!(NodeRenaming::<u32>::load(&folder_name).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn node_renaming_serde() {
+        let renaming = NodeRenaming::compress([(10u32, 3u32)].into_iter());
+        let json = serde_json::to_string(&renaming).unwrap();
+
+        assert_eq!(json, "{\"original\":[3,10]}");
+        assert_eq!(
+            serde_json::from_str::<NodeRenaming<u32>>(&json)
+                .unwrap()
+                .original_to_new(10),
+            Some(1)
+        );
+    }
+}
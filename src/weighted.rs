@@ -0,0 +1,128 @@
+use std::io::{BufWriter, Write};
+
+use super::{
+    metadata::GraphMetadata,
+    reading,
+    storage::Storage,
+    util::{GraphData, ValidGraphType},
+    ConstructionHint, Graph,
+};
+
+/// The default name of the weight file inside a graph folder.
+pub const WEIGHT_NAME: &str = "weight.csr";
+
+/// A graph with a weight of type `W` attached to each edge.
+/// The weights are stored in [WEIGHT_NAME], next to the vertex and edge files, in the same order as the edges.
+pub struct WeightedGraph<'a, N, W> {
+    graph: Graph<'a, N>,
+    weights: Storage<'a, W>,
+}
+
+impl<'a, N, W> WeightedGraph<'a, N, W>
+where
+    N: ValidGraphType,
+    W: GraphData,
+{
+    /// Same as [Graph::from_adjacency_list], except each edge of the SORTED (by source) `stream` carries a weight.
+    /// The edges of each node are kept in the order of `stream`, instead of being sorted by destination.
+    pub fn from_weighted_adjacency_list<T>(
+        stream: T,
+        folder_name: &str,
+    ) -> Result<WeightedGraph<'a, N, W>, std::io::Error>
+    where
+        T: Iterator<Item = std::io::Result<(N, N, W)>> + Sized,
+    {
+        reading::create_folder(folder_name)?;
+        let weights_file = reading::get_file(folder_name, WEIGHT_NAME)?;
+        weights_file.set_len(0)?;
+
+        // The weights are written as the edges go through, so they end up in the same order
+        let mut weights_writer = BufWriter::new(&weights_file);
+        let stream = stream.map(|edge| {
+            let (src, dst, weight) = edge?;
+            weight.write_self(&mut weights_writer)?;
+            Ok((src, dst))
+        });
+        // The edges are kept in the order of the stream, so that they stay aligned with their weights
+        let files = reading::write_adjacency_list(
+            stream,
+            folder_name,
+            reading::VERTEX_NAME,
+            reading::EDGE_NAME,
+            ConstructionHint::CheckSorted,
+            0,
+            false,
+        )?;
+        GraphMetadata::new::<N>(files.2 - 1, files.3).save(folder_name)?;
+        weights_writer.flush()?;
+        drop(weights_writer);
+
+        Self::load_graph(folder_name)
+    }
+
+    /// Loads a weighted graph from the underlying representation.
+    /// Fails with `InvalidData` if the weight file does not have a weight for each edge.
+    pub fn load_graph(graph_folder: &str) -> Result<WeightedGraph<'a, N, W>, std::io::Error> {
+        let graph = Graph::load_graph(graph_folder)?;
+        let weights =
+            Storage::map_file_readonly(reading::open_file_readonly(graph_folder, WEIGHT_NAME)?);
+
+        if weights.len() != graph.n_edges() {
+            Err(std::io::ErrorKind::InvalidData)?;
+        }
+
+        Ok(WeightedGraph { graph, weights })
+    }
+
+    /// Returns the unweighted graph.
+    pub fn graph(&self) -> &Graph<'a, N> {
+        &self.graph
+    }
+
+    /// Returns the weights of all edges, in the same order as the edges.
+    pub fn weights(&self) -> &[W] {
+        self.weights.get_data_as_slice()
+    }
+
+    /// Returns the weights of the outgoing edges of `node`, in the same order as its [neighbors](Graph::neighbors).
+    pub fn edge_weights(&self, node: usize) -> &[W] {
+        &self.weights()[self.graph.nodes[node]..self.graph.nodes[node + 1]]
+    }
+
+    /// Returns the number of nodes existing in the graph
+    pub fn n_nodes(&self) -> usize {
+        self.graph.n_nodes()
+    }
+
+    /// Returns the number of edges existing in the graph
+    pub fn n_edges(&self) -> usize {
+        self.graph.n_edges()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weighted_construction() {
+        let edges = vec![(0u32, 1u32, 2.5f32), (0, 2, 1.0), (2, 0, 4.0)];
+        let destination_folder_name = format!("/tmp/tmp_dst_{}", rand::random::<u32>());
+
+        let graph = WeightedGraph::<u32, f32>::from_weighted_adjacency_list(
+            edges.into_iter().map(Ok),
+            &destination_folder_name,
+        )
+        .unwrap();
+
+        assert_eq!(graph.n_nodes(), 3);
+        assert_eq!(graph.graph().neighbors(0), &[1, 2]);
+        assert_eq!(graph.edge_weights(0), &[2.5, 1.0]);
+        assert_eq!(graph.edge_weights(1), &[] as &[f32]);
+        assert_eq!(graph.edge_weights(2), &[4.0]);
+
+        let loaded = WeightedGraph::<u32, f32>::load_graph(&destination_folder_name).unwrap();
+        assert_eq!(loaded.weights(), &[2.5, 1.0, 4.0]);
+        assert!(WeightedGraph::<u32, f64>::load_graph(&destination_folder_name).is_err());
+    }
+}